@@ -0,0 +1,202 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use std::str::FromStr;
+
+use crate::common::{CliError, NetworkId};
+
+
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A single file which carries a transaction between the online and the
+/// air-gapped machines: the online helper writes the nonce and the block hash,
+/// the offline side adds the unsigned transaction, the signer adds the
+/// signature, and the online side broadcasts it. The plain fields repeat what
+/// is inside the encoded transaction, so the file can be reviewed by eye.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TransactionBundle {
+    pub format_version: u32,
+    pub network_id: Option<NetworkId>,
+    pub signer_id: String,
+    pub public_key: String,
+    pub nonce: u64,
+    pub block_hash: String,
+    /// base64-encoded borsh serialization of the unsigned transaction
+    pub unsigned_transaction: Option<String>,
+    pub signature: Option<String>,
+    /// Seconds since the Unix epoch
+    pub created_at: u64,
+}
+
+impl TransactionBundle {
+    /// A bundle which only prepares the nonce and the block hash for an
+    /// offline transaction
+    pub fn prepare(
+        network_id: Option<NetworkId>,
+        signer_id: &str,
+        public_key: &near_crypto::PublicKey,
+        nonce: u64,
+        block_hash: &near_primitives::hash::CryptoHash,
+    ) -> Self {
+        TransactionBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            network_id,
+            signer_id: signer_id.to_string(),
+            public_key: public_key.to_string(),
+            nonce,
+            block_hash: block_hash.to_string(),
+            unsigned_transaction: None,
+            signature: None,
+            created_at: unix_timestamp(),
+        }
+    }
+
+    pub fn from_transaction(
+        network_id: Option<NetworkId>,
+        transaction: &near_primitives::transaction::Transaction,
+    ) -> Self {
+        TransactionBundle {
+            unsigned_transaction: Some(near_primitives::serialize::to_base64(
+                transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            )),
+            ..TransactionBundle::prepare(
+                network_id,
+                &transaction.signer_id,
+                &transaction.public_key,
+                transaction.nonce,
+                &transaction.block_hash,
+            )
+        }
+    }
+
+    pub fn read(path: &std::path::Path) -> Result<Self, CliError> {
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            CliError::IoError(format!("Failed to read the bundle {}: {}", path.display(), err))
+        })?;
+        let bundle: TransactionBundle = serde_json::from_str(&content).map_err(|err| {
+            CliError::ParseError(format!("{} is not a valid transaction bundle: {}", path.display(), err))
+        })?;
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(CliError::ParseError(format!(
+                "The bundle {} has an unsupported format version {}",
+                path.display(),
+                bundle.format_version
+            )));
+        };
+        Ok(bundle)
+    }
+
+    pub fn write(&self, path: &std::path::Path) -> Result<(), CliError> {
+        let content = serde_json::to_string_pretty(self)
+            .expect("Transaction bundle is not expected to fail on serialization");
+        std::fs::write(path, content).map_err(|err| {
+            CliError::IoError(format!("Failed to write the bundle {}: {}", path.display(), err))
+        })
+    }
+
+    pub fn block_hash(&self) -> Result<near_primitives::hash::CryptoHash, CliError> {
+        crate::common::BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(&self.block_hash)
+            .map(|block_hash| block_hash.into_inner())
+            .map_err(|err| CliError::ParseError(format!("The bundle has an invalid block hash: {}", err)))
+    }
+
+    pub fn public_key(&self) -> Result<near_crypto::PublicKey, CliError> {
+        near_crypto::PublicKey::from_str(&self.public_key)
+            .map_err(|err| CliError::ParseError(format!("The bundle has an invalid public key: {:?}", err)))
+    }
+
+    /// Decodes the unsigned transaction and makes sure the plain fields tell
+    /// the truth about it
+    pub fn transaction(&self) -> Result<near_primitives::transaction::Transaction, CliError> {
+        let encoded_transaction = self.unsigned_transaction.as_ref().ok_or_else(|| {
+            CliError::ParseError("The bundle does not contain a transaction yet".to_string())
+        })?;
+        let transaction = crate::common::TransactionFormat::Base64
+            .decode(encoded_transaction)
+            .and_then(|serialized_transaction| {
+                near_primitives::transaction::Transaction::try_from_slice(&serialized_transaction)
+                    .map_err(|err| err.to_string())
+            })
+            .map_err(|err| CliError::ParseError(format!("The bundle has an invalid transaction: {}", err)))?;
+        if transaction.signer_id != self.signer_id
+            || transaction.public_key.to_string() != self.public_key
+            || transaction.nonce != self.nonce
+            || transaction.block_hash.to_string() != self.block_hash
+        {
+            return Err(CliError::ParseError(
+                "The bundle fields do not match the transaction inside it".to_string(),
+            ));
+        };
+        Ok(transaction)
+    }
+
+    /// Stores the signature after checking it against the transaction
+    pub fn set_signature(&mut self, signature: &near_crypto::Signature) -> Result<(), CliError> {
+        let transaction = self.transaction()?;
+        if !signature.verify(transaction.get_hash().as_ref(), &transaction.public_key) {
+            return Err(CliError::SigningError(
+                "The signature does not match the transaction in the bundle".to_string(),
+            ));
+        };
+        self.signature = Some(signature.to_string());
+        Ok(())
+    }
+
+    pub fn signed_transaction(&self) -> Result<near_primitives::transaction::SignedTransaction, CliError> {
+        let transaction = self.transaction()?;
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| CliError::ParseError("The bundle has not been signed yet".to_string()))
+            .and_then(|signature| {
+                near_crypto::Signature::from_str(signature).map_err(|err| {
+                    CliError::ParseError(format!("The bundle has an invalid signature: {:?}", err))
+                })
+            })?;
+        if !signature.verify(transaction.get_hash().as_ref(), &transaction.public_key) {
+            return Err(CliError::SigningError(
+                "The signature does not match the transaction in the bundle".to_string(),
+            ));
+        };
+        Ok(near_primitives::transaction::SignedTransaction::new(signature, transaction))
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_signature_roundtrip() {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let transaction = near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: secret_key.public_key(),
+            nonce: 56,
+            receiver_id: "qwe.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![],
+        };
+        let mut bundle = TransactionBundle::from_transaction(Some(NetworkId::Testnet), &transaction);
+        assert!(bundle.signed_transaction().is_err());
+
+        let other_secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        assert!(bundle
+            .set_signature(&other_secret_key.sign(transaction.get_hash().as_ref()))
+            .is_err());
+
+        bundle
+            .set_signature(&secret_key.sign(transaction.get_hash().as_ref()))
+            .unwrap();
+        let bundle: TransactionBundle =
+            serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert_eq!(bundle.signed_transaction().unwrap().transaction, transaction);
+    }
+}
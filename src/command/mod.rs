@@ -6,24 +6,33 @@ use strum::VariantNames;
 use dialoguer::{
     Select,
     theme::ColorfulTheme,
-    console::Term
 };
 
 mod on_off_line_mode;
 use on_off_line_mode::{CliOnOffLineMode, Mode, OnOffLineMode};
+pub mod select_network;
+mod send_signed_transaction_command;
+use send_signed_transaction_command::{CliSendSignedTransaction, SendSignedTransaction};
+mod sign_unsigned_transaction_command;
+use sign_unsigned_transaction_command::{CliSignUnsignedTransaction, SignUnsignedTransaction};
+use crate::utils_subcommand::{CliUtils, Util, Utils};
 
 
 #[derive(Debug, EnumVariantNames, StructOpt)]
 #[strum(serialize_all = "kebab_case")]
 pub enum CliCommand {
     ConstructTransactionCommand(CliOnOffLineMode),
-    Utils,
+    Utils(CliUtils),
+    SendSignedTransactionCommand(CliSendSignedTransaction),
+    SignUnsignedTransactionCommand(CliSignUnsignedTransaction),
 }
 
 #[derive(Debug, EnumVariantNames)]
 pub enum ArgsCommand {
     ConstructTransactionCommand(OnOffLineMode),
-    Utils,
+    Utils(Utils),
+    SendSignedTransactionCommand(SendSignedTransaction),
+    SignUnsignedTransactionCommand(SignUnsignedTransaction),
 }
 
 impl From<CliCommand> for ArgsCommand {
@@ -33,34 +42,63 @@ impl From<CliCommand> for ArgsCommand {
                 let onoffline_mode = OnOffLineMode::from(cli_onoffline_mode);
                 ArgsCommand::ConstructTransactionCommand(onoffline_mode)
             }
-            CliCommand::Utils => ArgsCommand::Utils,
+            CliCommand::Utils(cli_utils) => {
+                let utils = Utils::from(cli_utils);
+                ArgsCommand::Utils(utils)
+            }
+            CliCommand::SendSignedTransactionCommand(cli_send_signed_transaction) => {
+                let send_signed_transaction = SendSignedTransaction::from(cli_send_signed_transaction);
+                ArgsCommand::SendSignedTransactionCommand(send_signed_transaction)
+            }
+            CliCommand::SignUnsignedTransactionCommand(cli_sign_unsigned_transaction) => {
+                let sign_unsigned_transaction = SignUnsignedTransaction::from(cli_sign_unsigned_transaction);
+                ArgsCommand::SignUnsignedTransactionCommand(sign_unsigned_transaction)
+            }
         }
     }
 }
 
 impl ArgsCommand {
-    pub fn choose_command() -> Self {
-        println!();
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Choose your action")
-            .item("ConstructTransactionCommand")
-            .item("Utils")
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
-        println!("------------  {:?}", &selection);
-        // let ee = commands.binary_search(&"ConstructTransactionCommand").unwrap();
-        // println!("    ee    {}", ee);
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        let (subcommand, args) = match self {
+            ArgsCommand::ConstructTransactionCommand(mode) => ("construct-transaction-command", mode.to_cli_args()),
+            ArgsCommand::Utils(utils) => ("utils", crate::common::CliCommandArgs::complete(utils.to_cli_args())),
+            ArgsCommand::SendSignedTransactionCommand(send_signed_transaction) => (
+                "send-signed-transaction-command",
+                crate::common::CliCommandArgs::complete(send_signed_transaction.to_cli_args()),
+            ),
+            ArgsCommand::SignUnsignedTransactionCommand(sign_unsigned_transaction) => (
+                "sign-unsigned-transaction-command",
+                crate::common::CliCommandArgs::complete(sign_unsigned_transaction.to_cli_args()),
+            ),
+        };
+        args.prefixed(vec![subcommand.to_string()])
+    }
+    pub fn choose_command() -> Result<Self, crate::common::CliError> {
+        eprintln!();
+        let selection = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Choose your action")
+                .item("ConstructTransactionCommand")
+                .item("Utils")
+                .item("SendSignedTransactionCommand")
+                .item("SignUnsignedTransactionCommand")
+                .default(0),
+        )?;
+        log::trace!("------------  {:?}", &selection);
         match selection {
-            Some(0) => {
-                Self::ConstructTransactionCommand(OnOffLineMode{mode: Mode::choose_mode()})
+            0 => {
+                Ok(Self::ConstructTransactionCommand(OnOffLineMode{mode: Mode::choose_mode()?}))
+            },
+            1 => {
+                Ok(Self::Utils(Utils{util: Util::choose_util()?}))
+            },
+            2 => {
+                Ok(Self::SendSignedTransactionCommand(SendSignedTransaction::choose_send_signed_transaction()?))
             },
-            Some(1) => {
-                Self::Utils
+            3 => {
+                Ok(Self::SignUnsignedTransactionCommand(SignUnsignedTransaction::choose_sign_unsigned_transaction()?))
             },
-            // Some(commands.binary_search(&"ConstructTransactionCommand").unwrap()) => {
-            //     Self::ConstructTransactionCommand(OnOffLineMode{mode: Mode::choose_mode()})
-            // },
             _ => unreachable!("Error")
         }
     }
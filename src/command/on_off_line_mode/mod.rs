@@ -4,8 +4,8 @@ use dialoguer::{
     Select,
     Input,
     theme::ColorfulTheme,
-    console::Term
 };
+use crate::common::PromptAnswer;
 use near_primitives::hash::CryptoHash;
 
 mod select_server;
@@ -35,16 +35,23 @@ pub struct OnOffLineMode {
 }
 
 impl OnOffLineMode {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        match &self.mode {
+            Mode::Online(online_args) => online_args.to_cli_args().prefixed(vec!["online".to_string()]),
+            Mode::Offline(offline_args) => offline_args.to_cli_args().prefixed(vec!["offline".to_string()]),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-    ) {
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
         match self.mode {
             Mode::Online(online_args) => {
-                online_args.process(prepopulated_unsigned_transaction).await
+                online_args.process(prepopulated_unsigned_transaction, output_format).await
             },
             Mode::Offline(offline_args) => {
-                offline_args.process(prepopulated_unsigned_transaction).await
+                offline_args.process(prepopulated_unsigned_transaction, output_format).await
             },
         }
     }
@@ -54,7 +61,7 @@ impl From<CliOnOffLineMode> for OnOffLineMode {
     fn from(item: CliOnOffLineMode) -> Self {
         let mode = match item.mode {
             Some(cli_mode) => Mode::from(cli_mode),
-            None => Mode::choose_mode()
+            None => Mode::choose_mode().unwrap_or_else(|err| err.exit())
         };
         Self { mode }
     }
@@ -67,37 +74,39 @@ pub enum Mode {
 }
 
 impl Mode {
-    pub fn choose_mode() -> Self {
+    pub fn choose_mode() -> Result<Self, crate::common::CliError> {
         let choose_mode= vec![
             "Yes, I keep it simple",
             "No, I want to work in no-network (air-gapped) environment"
         ];
-        println!();
-        let select_mode = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt(
-                "To construct a transaction you will need to provide information about sender (signer) and receiver accounts, and actions that needs to be performed.
-                 \nDo you want to derive some information required for transaction construction automatically querying it online?"
-            )
-            .items(&choose_mode)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        eprintln!();
+        let select_mode = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(
+                    "To construct a transaction you will need to provide information about sender (signer) and receiver accounts, and actions that needs to be performed.
+                     \nDo you want to derive some information required for transaction construction automatically querying it online?"
+                )
+                .items(&choose_mode)
+                .default(0),
+        )?;
         match select_mode {
-            Some(0) => {
-                let selected_server: SelectServer = SelectServer::select_server();
-                Mode::Online(OnlineArgs {
+            0 => {
+                let selected_server: SelectServer = SelectServer::select_server()?;
+                Ok(Mode::Online(OnlineArgs {
                         selected_server
-                    }) 
+                    }))
             },
-            Some(1) => {
-                let nonce: u64 = OfflineArgs::input_nonce();
-                let block_hash = OfflineArgs::input_block_hash();
-                let send_from: SendFrom = SendFrom::send_from();
-                Mode::Offline(OfflineArgs {
+            1 => {
+                let nonce: u64 = OfflineArgs::input_nonce()?;
+                let block_hash = OfflineArgs::input_block_hash()?;
+                let send_from: SendFrom = SendFrom::send_from()?;
+                Ok(Mode::Offline(OfflineArgs {
                     nonce,
                     block_hash,
+                    public_key: None,
+                    bundle: None,
                     send_from
-                })
+                }))
             }
             _ => unreachable!("Error")
         }
@@ -108,6 +117,9 @@ impl Mode {
 pub struct OfflineArgs {
     nonce: u64,
     block_hash: CryptoHash,
+    /// The key which a bundle has prepared the nonce for
+    public_key: Option<near_crypto::PublicKey>,
+    bundle: Option<std::path::PathBuf>,
     send_from: SendFrom
 }
 
@@ -117,6 +129,10 @@ pub struct CliOfflineArgs {
     nonce: Option<u64>,
     #[structopt(long)]
     block_hash: Option<crate::common::BlobAsBase58String<CryptoHash>>,
+    /// Take the nonce, the block hash, the sender and the signer key from a
+    /// transaction bundle file prepared on a connected machine
+    #[structopt(long, parse(from_os_str), conflicts_with_all = &["nonce", "block-hash"])]
+    bundle: Option<std::path::PathBuf>,
     #[structopt(subcommand)]
     pub send_from: Option<CliSendFrom>
 }
@@ -136,7 +152,7 @@ impl From<CliOnlineArgs> for OnlineArgs {
     fn from(item: CliOnlineArgs) -> Self {
         let selected_server = match item.selected_server {
             Some(cli_selected_server) => SelectServer::from(cli_selected_server),
-            None => SelectServer::select_server()
+            None => SelectServer::select_server().unwrap_or_else(|err| err.exit())
         };
         OnlineArgs {
             selected_server
@@ -146,66 +162,105 @@ impl From<CliOnlineArgs> for OnlineArgs {
 
 impl From<CliOfflineArgs> for OfflineArgs {
     fn from(item: CliOfflineArgs) -> Self {
-        let nonce: u64 = match item.nonce {
+        let bundle = item.bundle.as_ref().map(|cli_bundle| {
+            crate::bundle::TransactionBundle::read(cli_bundle).unwrap_or_else(|err| err.exit())
+        });
+        let (cli_nonce, cli_block_hash, public_key) = match &bundle {
+            Some(bundle) => {
+                let block_hash = bundle.block_hash().unwrap_or_else(|err| err.exit());
+                let public_key = bundle.public_key().unwrap_or_else(|err| err.exit());
+                (Some(bundle.nonce), Some(block_hash), Some(public_key))
+            },
+            None => (item.nonce, item.block_hash.map(|cli_block_hash| cli_block_hash.into_inner()), None)
+        };
+        let nonce: u64 = match cli_nonce {
             Some(cli_nonce) => cli_nonce,
-            None => OfflineArgs::input_nonce()
+            None => OfflineArgs::input_nonce().unwrap_or_else(|err| err.exit())
         };
-        let block_hash = match item.block_hash {
-            Some(cli_block_hash) => cli_block_hash.into_inner(),
-            None => OfflineArgs::input_block_hash()
+        let block_hash = match cli_block_hash {
+            Some(cli_block_hash) => cli_block_hash,
+            None => OfflineArgs::input_block_hash().unwrap_or_else(|err| err.exit())
         };
-        let send_from: SendFrom = match item.send_from {
-            Some(cli_send_from) => SendFrom::from(cli_send_from),
-            None => SendFrom::send_from()
+        let send_from: SendFrom = match (bundle, item.send_from) {
+            (Some(bundle), cli_send_from) => SendFrom::from_bundle(cli_send_from, &bundle)
+                .unwrap_or_else(|err| err.exit()),
+            (None, Some(cli_send_from)) => SendFrom::from(cli_send_from),
+            (None, None) => SendFrom::send_from().unwrap_or_else(|err| err.exit())
         };
         OfflineArgs {
             nonce,
             block_hash,
+            public_key,
+            bundle: item.bundle,
             send_from
         }
     }
 }
 
 impl OfflineArgs {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        let args = match &self.bundle {
+            Some(bundle) => vec!["--bundle".to_string(), bundle.to_string_lossy().into_owned()],
+            None => vec![
+                "--nonce".to_string(),
+                self.nonce.to_string(),
+                "--block-hash".to_string(),
+                self.block_hash.to_string(),
+            ],
+        };
+        self.send_from.to_cli_args().prefixed(args)
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-    ) {
-        println!("OfflineArgs process self:\n        {:?}", &self);
-        println!("OfflineArgs process prepopulated_unsigned_transaction:\n        {:?}", prepopulated_unsigned_transaction);
-        let selected_server_url = "".to_string();
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("OfflineArgs process self:\n        {:?}", &self);
+        log::debug!("OfflineArgs process prepopulated_unsigned_transaction:\n        {:?}", prepopulated_unsigned_transaction);
         let nonce = self.nonce.clone();
         let block_hash = self.block_hash.clone();
+        let public_key = self.public_key.clone()
+            .unwrap_or_else(|| prepopulated_unsigned_transaction.public_key.clone());
         let unsigned_transaction = near_primitives::transaction::Transaction {                    
             block_hash,
             nonce,
+            public_key,
             .. prepopulated_unsigned_transaction
         };
-        self.send_from.process(unsigned_transaction, selected_server_url).await;
+        self.send_from.process(unsigned_transaction, None, output_format).await
     }
-    fn input_nonce() -> u64 {
+    fn input_nonce() -> Result<u64, crate::common::CliError> {
         Input::new()
-            .with_prompt("Enter transaction nonce (query the access key information with
-                `near-cli utils view-access-key frol4.testnet ed25519:...` incremented by 1)")
+            .with_prompt("Enter transaction nonce (run `utils view-access-key <account ID> <public key> <network>`
+                on a connected machine, it prints the next nonce)")
             .interact_text()
-            .unwrap()
+            .answer()
     }
-    fn input_block_hash() -> near_primitives::hash::CryptoHash {
+    fn input_block_hash() -> Result<near_primitives::hash::CryptoHash, crate::common::CliError> {
         let input_block_hash: String = Input::new()
-            .with_prompt("Enter recent block hash:")
+            .with_prompt("Enter recent block hash (run `utils latest-block <network>` on a connected machine)")
+            .validate_with(|input: &String| -> Result<(), String> {
+                crate::common::BlobAsBase58String::<CryptoHash>::from_str(input).map(|_| ())
+            })
             .interact_text()
-            .unwrap();
-        crate::common::BlobAsBase58String::<CryptoHash>::from_str(&input_block_hash).unwrap().into_inner()
+            .answer()?;
+        crate::common::BlobAsBase58String::<CryptoHash>::from_str(&input_block_hash)
+            .map(|block_hash| block_hash.into_inner())
+            .map_err(crate::common::CliError::ParseError)
     }
 }
 
 impl OnlineArgs {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        self.selected_server.to_cli_args()
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-    ) {
-        println!("OnlineArgs process:\n        {:?}", prepopulated_unsigned_transaction);
-        self.selected_server.process(prepopulated_unsigned_transaction).await;
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("OnlineArgs process:\n        {:?}", prepopulated_unsigned_transaction);
+        self.selected_server.process(prepopulated_unsigned_transaction, output_format).await
     }
 }
 
@@ -8,9 +8,10 @@ use dialoguer::{
     Select,
     Input,
     theme::ColorfulTheme,
-    console::Term
 };
+use crate::common::PromptAnswer;
 
+use crate::common::NetworkId;
 use crate::consts;
 use consts::{
     TESTNET_API_SERVER_URL,
@@ -61,51 +62,73 @@ impl From<CliSelectServer> for SelectServer {
 }
 
 impl SelectServer {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        let (server_args, server) = match self {
+            SelectServer::Testnet(server) => (vec!["testnet".to_string()], server),
+            SelectServer::Mainnet(server) => (vec!["mainnet".to_string()], server),
+            SelectServer::Betanet(server) => (vec!["betanet".to_string()], server),
+            SelectServer::Custom(server) => {
+                (vec!["custom".to_string(), "--url".to_string(), server.url.clone()], server)
+            },
+        };
+        server.send_from.to_cli_args().prefixed(server_args)
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-    ) {
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
         match self {
             SelectServer::Testnet(server) => {
-                server.process(prepopulated_unsigned_transaction).await;
+                server.process(prepopulated_unsigned_transaction, NetworkId::Testnet, output_format).await
+            },
+            SelectServer::Mainnet(server) => {
+                server.process(prepopulated_unsigned_transaction, NetworkId::Mainnet, output_format).await
+            },
+            SelectServer::Betanet(server) => {
+                server.process(prepopulated_unsigned_transaction, NetworkId::Betanet, output_format).await
+            },
+            SelectServer::Custom(server) => {
+                server.process(prepopulated_unsigned_transaction, NetworkId::Custom, output_format).await
             },
-            SelectServer::Mainnet(_server) => {},
-            SelectServer::Betanet(_server) => {},
-            SelectServer::Custom(_server) => {},
         }
     }
-    pub fn select_server() -> Self {
-        println!();
+    pub fn select_server() -> Result<Self, crate::common::CliError> {
+        eprintln!();
         let servers= SelectServer::VARIANTS;
-        let select_server = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select NEAR protocol RPC server:")
-            .items(&servers)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
-        let send_from = SendFrom::send_from();
-        match select_server {
-            Some(0) => SelectServer::Testnet(Server{
+        let select_server = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select NEAR protocol RPC server:")
+                .items(&servers)
+                .default(0),
+        )?;
+        let custom_url = match select_server {
+            3 => Some(
+                Input::new()
+                    .with_prompt("What is the RPC endpoint?")
+                    .interact_text()
+                    .answer()?
+            ),
+            _ => None
+        };
+        let send_from = SendFrom::send_from()?;
+        match (select_server, custom_url) {
+            (0, _) => Ok(SelectServer::Testnet(Server{
                             url: TESTNET_API_SERVER_URL.to_string(),
                             send_from
-                        }),
-            Some(1) => SelectServer::Mainnet(Server{
+                        })),
+            (1, _) => Ok(SelectServer::Mainnet(Server{
                             url: MAINNET_API_SERVER_URL.to_string(),
                             send_from
-                        }),
-            Some(2) => SelectServer::Betanet(Server{
+                        })),
+            (2, _) => Ok(SelectServer::Betanet(Server{
                             url: BETANET_API_SERVER_URL.to_string(),
                             send_from
-                        }),
-            Some(3) => SelectServer::Custom(Server{
-                            url: {
-                                Input::new()
-                                .with_prompt("What is the RPC endpoint?")
-                                .interact_text()
-                                .unwrap()
-                            },
+                        })),
+            (3, Some(url)) => Ok(SelectServer::Custom(Server{
+                            url,
                             send_from
-            }),
+            })),
             _ => unreachable!("Error")
         }
     }
@@ -1,7 +1,9 @@
 use structopt::StructOpt;
+use std::str::FromStr;
 use dialoguer::{
     Input,
 };
+use crate::common::PromptAnswer;
 
 mod sender;
 use sender::{CliSender, SendTo, Sender};
@@ -17,10 +19,15 @@ impl Server {
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-    ) {
-        println!("Server process:\n        {:?}", &self);
-        let selected_server_url: String = self.url.clone();
-        self.send_from.process(prepopulated_unsigned_transaction, selected_server_url).await;
+        network_id: crate::common::NetworkId,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("Server process:\n        {:?}", &self);
+        let connection_config = crate::common::ConnectionConfig {
+            network_id,
+            url: self.url.clone(),
+        };
+        self.send_from.process(prepopulated_unsigned_transaction, Some(connection_config), output_format).await
     }
 }
 
@@ -30,14 +37,20 @@ pub enum SendFrom {
 }
 
 impl SendFrom {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        match self {
+            SendFrom::Sender(sender) => sender.to_cli_args().prefixed(vec!["sender".to_string()]),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("Sendfrom process:\n      {:?}", &self);
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("Sendfrom process:\n      {:?}", &self);
         match self {
-            SendFrom::Sender(sender) => sender.process(prepopulated_unsigned_transaction, selected_server_url).await,
+            SendFrom::Sender(sender) => sender.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
         }
     }
 }
@@ -65,7 +78,7 @@ impl CliServer {
     pub fn into_server(self, url: String) -> Server {
         let send_from: SendFrom = match self.send_from {
             Some(cli_send_from) => SendFrom::from(cli_send_from),
-            None => SendFrom::send_from()
+            None => SendFrom::send_from().unwrap_or_else(|err| err.exit())
         };
         Server {
             url,
@@ -80,14 +93,15 @@ impl CliCustomServer {
             Some(url) => url,
             None => {
                 Input::new()
-                    .with_prompt("What is the RPC endpoi?")
+                    .with_prompt("What is the RPC endpoint?")
                     .interact_text()
-                    .unwrap()
+                    .answer()
+                    .unwrap_or_else(|err| err.exit())
             }
         };
         let send_from: SendFrom = match self.send_from {
             Some(cli_send_from) => SendFrom::from(cli_send_from),
-            None => SendFrom::send_from()
+            None => SendFrom::send_from().unwrap_or_else(|err| err.exit())
         };
         Server {
             url,
@@ -108,12 +122,42 @@ impl From<CliSendFrom> for SendFrom {
 }
 
 impl SendFrom {
-    pub fn send_from() -> Self {
-        let sender_account_id : String = Sender::input_sender_account_id();
-        let send_to: SendTo = SendTo::send_to();
-        SendFrom::Sender(Sender {
+    /// The sender which the bundle has been prepared for is not asked for
+    /// again, and any other sender is refused
+    pub fn from_bundle(
+        cli_send_from: Option<CliSendFrom>,
+        bundle: &crate::bundle::TransactionBundle,
+    ) -> Result<Self, crate::common::CliError> {
+        match cli_send_from {
+            Some(CliSendFrom::Sender(mut cli_sender)) => {
+                let bundle_sender_account_id = crate::common::ValidAccountId::from_str(&bundle.signer_id)
+                    .map_err(crate::common::CliError::ParseError)?;
+                if let Some(cli_sender_account_id) = cli_sender.sender_account_id.replace(bundle_sender_account_id) {
+                    let cli_sender_account_id = cli_sender_account_id.into_inner();
+                    if cli_sender_account_id != bundle.signer_id {
+                        return Err(crate::common::CliError::ParseError(format!(
+                            "The bundle has been prepared for {}, not for {}",
+                            bundle.signer_id, cli_sender_account_id
+                        )));
+                    };
+                };
+                Ok(SendFrom::from(CliSendFrom::Sender(cli_sender)))
+            }
+            None => {
+                let send_to: SendTo = SendTo::send_to()?;
+                Ok(SendFrom::Sender(Sender {
+                    sender_account_id: bundle.signer_id.clone(),
+                    send_to
+                }))
+            }
+        }
+    }
+    pub fn send_from() -> Result<Self, crate::common::CliError> {
+        let sender_account_id : String = Sender::input_sender_account_id()?;
+        let send_to: SendTo = SendTo::send_to()?;
+        Ok(SendFrom::Sender(Sender {
             sender_account_id,
             send_to
-        })
+        }))
     }
 }
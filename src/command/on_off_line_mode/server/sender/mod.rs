@@ -2,6 +2,7 @@ use structopt::StructOpt;
 use dialoguer::{
     Input,
 };
+use crate::common::PromptAnswer;
 
 mod receiver;
 use receiver::{
@@ -24,7 +25,7 @@ pub enum SendTo {
 
 #[derive(Debug, StructOpt)]
 pub struct CliSender {
-    pub sender_account_id: Option<String>,
+    pub sender_account_id: Option<crate::common::ValidAccountId>,
     #[structopt(subcommand)]
     send_to: Option<CliSendTo> 
 }
@@ -34,36 +35,41 @@ pub enum CliSendTo {
 }
 
 impl Sender {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        self.send_to.to_cli_args().prefixed(vec![self.sender_account_id.clone()])
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
             .. prepopulated_unsigned_transaction
         };
-        self.send_to.process(unsigned_transaction, selected_server_url).await;
+        self.send_to.process(unsigned_transaction, connection_config, output_format).await
     }
-    pub fn input_sender_account_id() -> String {
-        println!();
-        Input::new()
+    pub fn input_sender_account_id() -> Result<String, crate::common::CliError> {
+        eprintln!();
+        let sender_account_id: crate::common::ValidAccountId = Input::new()
             .with_prompt("What is the account ID of the sender?")
             .interact_text()
-            .unwrap()
+            .answer()?;
+        Ok(sender_account_id.into_inner())
     }
 }
 
 impl From<CliSender> for Sender {
     fn from(item: CliSender) -> Self {
         let sender_account_id: String = match item.sender_account_id {
-            Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id()
+            Some(cli_sender_account_id) => cli_sender_account_id.into_inner(),
+            None => Sender::input_sender_account_id().unwrap_or_else(|err| err.exit())
         };
         let send_to: SendTo = match item.send_to {
             Some(cli_send_to) => SendTo::from(cli_send_to),
-            None => SendTo::send_to()
-        }; 
+            None => SendTo::send_to().unwrap_or_else(|err| err.exit())
+        };
         Sender {
             sender_account_id,
             send_to
@@ -72,22 +78,28 @@ impl From<CliSender> for Sender {
 }
 
 impl SendTo {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        match self {
+            SendTo::Receiver(receiver) => receiver.to_cli_args().prefixed(vec!["receiver".to_string()]),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
         match self {
-            SendTo::Receiver(receiver) => receiver.process(prepopulated_unsigned_transaction, selected_server_url).await
+            SendTo::Receiver(receiver) => receiver.process(prepopulated_unsigned_transaction, connection_config, output_format).await
         }
     }
-    pub fn send_to() -> Self {
-        let receiver_account_id: String = Receiver::input_receiver_account_id();
-        let transaction_subcommand: ActionSubcommand = ActionSubcommand::choose_action_command();
-        SendTo::Receiver(Receiver {
+    pub fn send_to() -> Result<Self, crate::common::CliError> {
+        let receiver_account_id: String = Receiver::input_receiver_account_id()?;
+        let transaction_subcommand: ActionSubcommand = ActionSubcommand::choose_action_command()?;
+        Ok(SendTo::Receiver(Receiver {
             receiver_account_id,
             transaction_subcommand
-        })
+        }))
     }
 }
 
@@ -7,14 +7,13 @@ use dialoguer::{
     Select,
     Input,
     theme::ColorfulTheme,
-    console::Term
 };
+use crate::common::PromptAnswer;
 
 mod transaction_actions;
 use transaction_actions::transfer_near_tokens_type::{
     TransferNEARTokensAction,
     CliTransferNEARTokensAction,
-    NearBalance
 };
 mod sign_transaction;
 use sign_transaction::{
@@ -39,6 +38,18 @@ use transaction_actions::delete_account_type::{
     DeleteAccountAction,
     CliDeleteAccountAction
 };
+use transaction_actions::call_function_type::{
+    CallFunctionAction,
+    CliCallFunctionAction
+};
+use transaction_actions::stake_near_tokens_type::{
+    StakeNEARTokensAction,
+    CliStakeNEARTokensAction
+};
+use transaction_actions::deploy_contract_type::{
+    DeployContractAction,
+    CliDeployContractAction
+};
 // use crate::command::on_off_line_mode::server::sender::receiver::add_access_key_type::full_access_type::FullAccessType;
 // use crate::utils_subcommand::generate_keypair_subcommand;
 // use add_access_key_type::full_access_type::FullAccessType;
@@ -53,18 +64,19 @@ pub struct Receiver {
 #[derive(Debug, EnumVariantNames)]
 pub enum ActionSubcommand {
     TransferNEARTokens(TransferNEARTokensAction),
-    CallFunction,
-    StakeNEARTokens,
+    CallFunction(CallFunctionAction),
+    StakeNEARTokens(StakeNEARTokensAction),
     CreateAccount(CreateAccountAction),
     DeleteAccount(DeleteAccountAction),
     AddAccessKey(AddAccessKeyAction),
     DeleteAccessKey(DeleteAccessKeyAction),
+    DeployContract(DeployContractAction),
     Skip(SkipAction)
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliReceiver {
-    receiver_account_id: Option<String>,
+    receiver_account_id: Option<crate::common::ValidAccountId>,
     #[structopt(subcommand)]
     transaction_subcommand: Option<CliActionSubcommand> 
 }
@@ -72,12 +84,13 @@ pub struct CliReceiver {
 #[derive(Debug, StructOpt)]
 pub enum CliActionSubcommand {
     TransferNEARTokens(CliTransferNEARTokensAction),
-    CallFunction,
-    StakeNEARTokens,
+    CallFunction(CliCallFunctionAction),
+    StakeNEARTokens(CliStakeNEARTokensAction),
     CreateAccount(CliCreateAccountAction),
     DeleteAccount(CliDeleteAccountAction),
     AddAccessKey(CliAddAccessKeyAction),
     DeleteAccessKey(CliDeleteAccessKeyAction),
+    DeployContract(CliDeployContractAction),
     Skip(CliSkipAction)
 }
 
@@ -87,111 +100,178 @@ pub enum CliActionSkipSubcommand {
 }
 
 impl ActionSubcommand {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (subcommand, args) = match self {
+            ActionSubcommand::TransferNEARTokens(args_transfer) => ("transfer-near-tokens", args_transfer.to_cli_args()?),
+            ActionSubcommand::CallFunction(args_function) => ("call-function", args_function.to_cli_args()?),
+            ActionSubcommand::StakeNEARTokens(args_stake) => ("stake-near-tokens", args_stake.to_cli_args()?),
+            ActionSubcommand::CreateAccount(args_create_account) => ("create-account", args_create_account.to_cli_args()?),
+            ActionSubcommand::DeleteAccount(args_delete_account) => ("delete-account", args_delete_account.to_cli_args()?),
+            ActionSubcommand::AddAccessKey(args_add_access_key) => ("add-access-key", args_add_access_key.to_cli_args()?),
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => ("delete-access-key", args_delete_access_key.to_cli_args()?),
+            ActionSubcommand::DeployContract(args_deploy_contract) => ("deploy-contract", args_deploy_contract.to_cli_args()?),
+            ActionSubcommand::Skip(args_skip) => ("skip", args_skip.to_cli_args()),
+        };
+        Some(std::iter::once(subcommand.to_string()).chain(args).collect())
+    }
+    /// `CliActionSkipSubcommand`, which follows an action on the command
+    /// line, only allows `skip`: the command line holds a single action, so
+    /// there is no command for an action which is followed by another one
+    /// (the receiver then prints the command up to the actions)
+    pub fn to_cli_skip_args(&self) -> Option<Vec<String>> {
+        match self {
+            ActionSubcommand::Skip(args_skip) => {
+                Some(std::iter::once("skip".to_string()).chain(args_skip.to_cli_args()).collect())
+            },
+            _ => None
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
         match self {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(prepopulated_unsigned_transaction, selected_server_url, "".to_string()).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
         }
     }
-    pub fn choose_action_command() -> Self {
-        println!();
+    pub fn choose_action_command() -> Result<Self, crate::common::CliError> {
+        eprintln!();
         let action_subcommands= ActionSubcommand::VARIANTS;
-        let select_action_subcommand = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select an action that you want to add to the action:")
-            .items(&action_subcommands)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let select_action_subcommand = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select an action that you want to add to the action:")
+                .items(&action_subcommands)
+                .default(0),
+        )?;
         match select_action_subcommand {
-            Some(0) => {
-                let amount: NearBalance = NearBalance::input_amount();
-                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command());
-                ActionSubcommand::TransferNEARTokens(TransferNEARTokensAction {
+            0 => {
+                let amount: crate::common::NearBalance = TransferNEARTokensAction::input_amount()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::TransferNEARTokens(TransferNEARTokensAction {
                     amount,
                     next_action
-                })
+                }))
+            },
+            1 => {
+                let method_name: String = CallFunctionAction::input_method_name()?;
+                let args: Vec<u8> = CallFunctionAction::input_args()?;
+                let gas: near_primitives::types::Gas = CallFunctionAction::input_gas()?;
+                let deposit: near_primitives::types::Balance = CallFunctionAction::input_deposit()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::CallFunction(CallFunctionAction {
+                    method_name,
+                    args,
+                    gas,
+                    deposit,
+                    next_action
+                }))
+            },
+            2 => {
+                let stake: crate::common::NearBalance = StakeNEARTokensAction::input_stake()?;
+                let public_key: near_crypto::PublicKey = StakeNEARTokensAction::input_public_key()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::StakeNEARTokens(StakeNEARTokensAction {
+                    stake,
+                    public_key,
+                    next_action
+                }))
             },
-            Some(1) => ActionSubcommand::CallFunction,
-            Some(2) => ActionSubcommand::StakeNEARTokens,
-            Some(3) => {
-                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command());
-                ActionSubcommand::CreateAccount(CreateAccountAction {
+            3 => {
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::CreateAccount(CreateAccountAction {
                     next_action
-                })
+                }))
             },
-            Some(4) => {
-                let beneficiary_id: String = DeleteAccountAction::input_beneficiary_id();
-                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command());
-                ActionSubcommand::DeleteAccount(DeleteAccountAction {
+            4 => {
+                let beneficiary_id: String = DeleteAccountAction::input_beneficiary_id()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::DeleteAccount(DeleteAccountAction {
                     beneficiary_id,
                     next_action
-                })
+                }))
             },
-            Some(5) => {
-                let public_key: String = AddAccessKeyAction::input_public_key();
-                let nonce: near_primitives::types::Nonce = AddAccessKeyAction::input_nonce();
-                let permission: AccessKeyPermission = AccessKeyPermission::choose_permission();
-                ActionSubcommand::AddAccessKey(AddAccessKeyAction {
+            5 => {
+                let public_key: near_crypto::PublicKey = AddAccessKeyAction::input_public_key()?;
+                let nonce: near_primitives::types::Nonce = AddAccessKeyAction::input_nonce()?;
+                let permission: AccessKeyPermission = AccessKeyPermission::choose_permission()?;
+                Ok(ActionSubcommand::AddAccessKey(AddAccessKeyAction {
                     public_key,
                     nonce,
                     permission
-                })
+                }))
             },
-            Some(6) => {
-                let public_key: String = DeleteAccessKeyAction::input_public_key();
-                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command());
-                ActionSubcommand::DeleteAccessKey(DeleteAccessKeyAction {
+            6 => {
+                let public_key: near_crypto::PublicKey = DeleteAccessKeyAction::input_public_key()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::DeleteAccessKey(DeleteAccessKeyAction {
                     public_key,
                     next_action
-                })
+                }))
+            },
+            7 => {
+                let (file_path, code) = DeployContractAction::input_wasm_file()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(ActionSubcommand::DeployContract(DeployContractAction {
+                    file_path,
+                    code,
+                    next_action
+                }))
             },
-            Some(7) => ActionSubcommand::Skip(SkipAction{sign_option: SignTransaction::choose_sign_option()}),
+            8 => Ok(ActionSubcommand::Skip(SkipAction{sign_option: SignTransaction::choose_sign_option()?})),
             _ => unreachable!("Error")
         }
     }
 }
 
 impl Receiver {
+    pub fn to_cli_args(&self) -> crate::common::CliCommandArgs {
+        let args = vec![self.receiver_account_id.clone()];
+        match self.transaction_subcommand.to_cli_args() {
+            Some(action_args) => crate::common::CliCommandArgs::complete(args.into_iter().chain(action_args).collect()),
+            None => crate::common::CliCommandArgs::partial(args),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("Receiver process: self:\n       {:?}", &self);
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("Receiver process: self:\n       {:?}", &self);
         let unsigned_transaction = near_primitives::transaction::Transaction {
             receiver_id: self.receiver_account_id.clone(),
             .. prepopulated_unsigned_transaction
         };
-        self.transaction_subcommand.process(unsigned_transaction, selected_server_url).await;
+        self.transaction_subcommand.process(unsigned_transaction, connection_config, output_format).await
     }
-    pub fn input_receiver_account_id() -> String {
-        Input::new()
+    pub fn input_receiver_account_id() -> Result<String, crate::common::CliError> {
+        let receiver_account_id: crate::common::ValidAccountId = Input::new()
             .with_prompt("What is the account ID of the receiver?")
             .interact_text()
-            .unwrap()
+            .answer()?;
+        Ok(receiver_account_id.into_inner())
     }
 }
 
 impl From<CliReceiver> for Receiver {
     fn from(item: CliReceiver) -> Self {
         let receiver_account_id: String = match item.receiver_account_id {
-            Some(cli_receiver_account_id) => cli_receiver_account_id,
-            None => Receiver::input_receiver_account_id()
+            Some(cli_receiver_account_id) => cli_receiver_account_id.into_inner(),
+            None => Receiver::input_receiver_account_id().unwrap_or_else(|err| err.exit())
         };
         let transaction_subcommand: ActionSubcommand = match item.transaction_subcommand {
             Some(cli_action_subcommand) => ActionSubcommand::from(cli_action_subcommand),
-            None => ActionSubcommand::choose_action_command()
+            None => ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit())
         };
         Receiver {
             receiver_account_id,
@@ -207,6 +287,14 @@ impl From<CliActionSubcommand> for ActionSubcommand {
                 let transfer_near_token: TransferNEARTokensAction = TransferNEARTokensAction::from(cli_transfer_near_token);
                 ActionSubcommand::TransferNEARTokens(transfer_near_token)
             },
+            CliActionSubcommand::CallFunction(cli_call_function) => {
+                let call_function: CallFunctionAction = CallFunctionAction::from(cli_call_function);
+                ActionSubcommand::CallFunction(call_function)
+            },
+            CliActionSubcommand::StakeNEARTokens(cli_stake_near_tokens) => {
+                let stake_near_tokens: StakeNEARTokensAction = StakeNEARTokensAction::from(cli_stake_near_tokens);
+                ActionSubcommand::StakeNEARTokens(stake_near_tokens)
+            },
             CliActionSubcommand::CreateAccount(cli_create_account) => {
                 let create_account: CreateAccountAction = CreateAccountAction::from(cli_create_account);
                 ActionSubcommand::CreateAccount(create_account)
@@ -223,7 +311,14 @@ impl From<CliActionSubcommand> for ActionSubcommand {
                 let delete_access_key: DeleteAccessKeyAction = DeleteAccessKeyAction::from(cli_delete_access_key);
                 ActionSubcommand::DeleteAccessKey(delete_access_key)
             },
-            _ => unreachable!("Error")
+            CliActionSubcommand::DeployContract(cli_deploy_contract) => {
+                let deploy_contract: DeployContractAction = DeployContractAction::from(cli_deploy_contract);
+                ActionSubcommand::DeployContract(deploy_contract)
+            },
+            CliActionSubcommand::Skip(cli_skip_action) => {
+                let skip_action: SkipAction = SkipAction::from(cli_skip_action);
+                ActionSubcommand::Skip(skip_action)
+            },
         }
     }
 }
@@ -247,6 +342,9 @@ mod tests {
 
     #[actix_rt::test]
     async fn test_receiver_process() {
+        use near_primitives::borsh::BorshDeserialize;
+
+        let output_file = std::env::temp_dir().join(format!("near-cli-receiver-test-{}.txt", std::process::id()));
         let my_self = Receiver {
             receiver_account_id: "qwe.testnet".to_string(),
             transaction_subcommand: ActionSubcommand::CreateAccount(
@@ -254,13 +352,15 @@ mod tests {
                     next_action: Box::new(ActionSubcommand::Skip(
                         SkipAction {
                             sign_option: SignTransaction::SignAlternative(sign_transaction::sign_alternative::SignAlternative{
-                                key_chain: "qweqwe".to_string()
+                                signer_public_key: near_crypto::PublicKey::from_str("ed25519:7FmDRADa1v4BcLiiR9MPPdmWQp3Um1iPdAYATvBY1YzS").unwrap(),
+                                format: crate::common::TransactionFormat::Base64,
+                                output_file: Some(output_file.clone()),
+                                bundle: None,
                             })
                         }
                     ))
                 }
             )
-            
         };
         let prepopulated_unsigned_transaction: near_primitives::transaction::Transaction = near_primitives::transaction::Transaction {
             signer_id: "volodymyr.testnet".to_string(),
@@ -270,9 +370,86 @@ mod tests {
             block_hash: crate::common::BlobAsBase58String::<CryptoHash>::from_str("F2KwJ2rBE5LfuPFPRTYtu243hTniYggfC6P24WQVfZnx").unwrap().into_inner(),
             actions: vec![],
         };
-        let selected_server_url: String = "https://rpc.testnet.near.org".to_string();
-        Receiver::process(my_self, prepopulated_unsigned_transaction, selected_server_url).await;
+        let expected_transaction = near_primitives::transaction::Transaction {
+            actions: vec![near_primitives::transaction::Action::CreateAccount(
+                near_primitives::transaction::CreateAccountAction {}
+            )],
+            .. prepopulated_unsigned_transaction.clone()
+        };
+        Receiver::process(my_self, prepopulated_unsigned_transaction, None, crate::common::OutputFormat::Plaintext).await.unwrap();
+        let encoded_transaction = std::fs::read_to_string(&output_file).unwrap();
+        std::fs::remove_file(&output_file).unwrap();
+        let serialized_transaction = crate::common::TransactionFormat::Base64.decode(&encoded_transaction).unwrap();
+        assert_eq!(
+            near_primitives::transaction::Transaction::try_from_slice(&serialized_transaction).unwrap(),
+            expected_transaction
+        );
+    }
 
+    #[test]
+    fn test_receiver_to_cli_args() {
+        let signer_secret_key = crate::common::SecretKey::from(
+            near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519)
+        );
+        let signer_public_key = signer_secret_key.public_key().to_string();
+        let my_self = Receiver {
+            receiver_account_id: "qwe.testnet".to_string(),
+            transaction_subcommand: ActionSubcommand::TransferNEARTokens(
+                TransferNEARTokensAction {
+                    amount: crate::common::NearBalance(10 * 10u128.pow(24)),
+                    next_action: Box::new(ActionSubcommand::Skip(
+                        SkipAction {
+                            sign_option: SignTransaction::SignPrivateKey(sign_transaction::sign_private_key::SignPrivateKey{
+                                signer_secret_key: signer_secret_key.clone(),
+                                yes: false,
+                            })
+                        }
+                    ))
+                }
+            )
+        };
+        assert_eq!(
+            my_self.to_cli_args(),
+            crate::common::CliCommandArgs::complete(vec![
+                "qwe.testnet",
+                "transfer-near-tokens",
+                "10 NEAR",
+                "skip",
+                "sign-private-key",
+                "--signer-public-key",
+                signer_public_key.as_str(),
+                "--signer-secret-key",
+                crate::common::REDACTED_SECRET,
+            ].into_iter().map(String::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_receiver_to_cli_args_several_actions() {
+        let my_self = Receiver {
+            receiver_account_id: "qwe.testnet".to_string(),
+            transaction_subcommand: ActionSubcommand::CreateAccount(
+                CreateAccountAction {
+                    next_action: Box::new(ActionSubcommand::TransferNEARTokens(
+                        TransferNEARTokensAction {
+                            amount: crate::common::NearBalance(10 * 10u128.pow(24)),
+                            next_action: Box::new(ActionSubcommand::Skip(
+                                SkipAction {
+                                    sign_option: SignTransaction::SignWithKeychain(sign_transaction::sign_keychain::SignKeychain{
+                                        network_id: None,
+                                        yes: false,
+                                    })
+                                }
+                            ))
+                        }
+                    ))
+                }
+            )
+        };
+        assert_eq!(
+            my_self.to_cli_args(),
+            crate::common::CliCommandArgs::partial(vec!["qwe.testnet".to_string()])
+        );
     }
 }
 
@@ -288,14 +465,18 @@ pub struct CliSkipAction {
 }
 
 impl SkipAction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.sign_option.to_cli_args()
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("Skip process:\n       {:?}", &self);
-        println!("Skip process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        self.sign_option.process(prepopulated_unsigned_transaction, selected_server_url).await;
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("Skip process:\n       {:?}", &self);
+        log::debug!("Skip process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        self.sign_option.process(prepopulated_unsigned_transaction, connection_config, output_format).await
     }
 }
 
@@ -303,7 +484,7 @@ impl From<CliSkipAction> for SkipAction {
     fn from(item: CliSkipAction) -> Self {
         let sign_option: SignTransaction = match item.sign_option {
             Some(cli_sign_transaction) => SignTransaction::from(cli_sign_transaction),
-            None => SignTransaction::choose_sign_option()
+            None => SignTransaction::choose_sign_option().unwrap_or_else(|err| err.exit())
         };
         SkipAction {sign_option}
     }
@@ -2,7 +2,6 @@ use structopt::StructOpt;
 use dialoguer::{
     Select,
     theme::ColorfulTheme,
-    console::Term
 };
 
 pub mod sign_private_key;
@@ -15,55 +14,96 @@ use sign_alternative::{
     SignAlternative,
     CliSignAlternative
 };
+pub mod sign_seed_phrase;
+use sign_seed_phrase::{
+    SignSeedPhrase,
+    CliSignSeedPhrase
+};
+pub mod sign_keychain;
+use sign_keychain::{
+    SignKeychain,
+    CliSignKeychain
+};
 
 
 #[derive(Debug)]
 pub enum SignTransaction {
     SignPrivateKey(SignPrivateKey),
+    SignSeedPhrase(SignSeedPhrase),
+    SignWithKeychain(SignKeychain),
     SignAlternative(SignAlternative)
 }
 
 #[derive(Debug, StructOpt)]
 pub enum CliSignTransaction {
     SignPrivateKey(CliSignPrivateKey),
+    /// Sign with a key derived from a BIP39 seed phrase
+    SignSeedPhrase(CliSignSeedPhrase),
+    /// Sign with the key stored for the signer in the local keychain
+    SignWithKeychain(CliSignKeychain),
     SignAlternative(CliSignAlternative)
 }
 
 impl SignTransaction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (subcommand, args) = match self {
+            SignTransaction::SignPrivateKey(keys) => ("sign-private-key", keys.to_cli_args()),
+            SignTransaction::SignSeedPhrase(sign_seed_phrase) => ("sign-seed-phrase", sign_seed_phrase.to_cli_args()),
+            SignTransaction::SignWithKeychain(sign_keychain) => ("sign-with-keychain", sign_keychain.to_cli_args()),
+            SignTransaction::SignAlternative(sign_alternative) => ("sign-alternative", sign_alternative.to_cli_args()),
+        };
+        std::iter::once(subcommand.to_string()).chain(args).collect()
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("SignTransaction process: self:       {:?}", &self);
-        println!("SignTransaction process: prepopulated_unsigned_transaction:       {:?}", &prepopulated_unsigned_transaction);
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("SignTransaction process: self:       {:?}", &self);
+        log::debug!("SignTransaction process: prepopulated_unsigned_transaction:       {:?}", &prepopulated_unsigned_transaction);
         match self {
-            SignTransaction::SignPrivateKey(keys) => keys.process(prepopulated_unsigned_transaction, selected_server_url).await,
-            SignTransaction::SignAlternative(chain) => chain.process(prepopulated_unsigned_transaction, selected_server_url)
+            SignTransaction::SignPrivateKey(keys) => keys.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            SignTransaction::SignSeedPhrase(sign_seed_phrase) => sign_seed_phrase.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            SignTransaction::SignWithKeychain(sign_keychain) => sign_keychain.process(prepopulated_unsigned_transaction, connection_config, output_format).await,
+            SignTransaction::SignAlternative(sign_alternative) => sign_alternative.process(prepopulated_unsigned_transaction, connection_config, output_format).await
         }
     }
-    pub fn choose_sign_option() -> Self {
-        println!();
+    pub fn choose_sign_option() -> Result<Self, crate::common::CliError> {
+        eprintln!();
         let sign_options = vec![
             "Yes, I want to sign the transaction with my private key",
+            "Yes, I want to sign the transaction with my seed phrase",
+            "Yes, I want to sign the transaction with the key from my keychain",
             "No, I want to construct the transaction and sign it somewhere else",
         ];
-        let select_sign_options = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Would you like to sign the transaction?")
-            .items(&sign_options)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let select_sign_options = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Would you like to sign the transaction?")
+                .items(&sign_options)
+                .default(0),
+        )?;
         match select_sign_options {
-            Some(0) => SignTransaction::SignPrivateKey(SignPrivateKey{
-                signer_public_key: SignPrivateKey::signer_public_key(),
-                signer_secret_key: SignPrivateKey::signer_secret_key()
-            }),
-            Some(1) => SignTransaction::SignAlternative(SignAlternative{key_chain: SignAlternative::input_key_chain()}),
-            _ => SignTransaction::SignPrivateKey(SignPrivateKey{
-                signer_public_key: SignPrivateKey::signer_public_key(),
-                signer_secret_key: SignPrivateKey::signer_secret_key()
-            })
+            0 => Ok(SignTransaction::SignPrivateKey(SignPrivateKey{
+                signer_secret_key: SignPrivateKey::signer_secret_key()?,
+                yes: false,
+            })),
+            1 => Ok(SignTransaction::SignSeedPhrase(SignSeedPhrase{
+                seed_phrase: SignSeedPhrase::input_seed_phrase()?,
+                seed_phrase_hd_path: crate::utils_subcommand::generate_keypair_subcommand::GenerateKeypair::input_seed_phrase_hd_path()?,
+                yes: false,
+            })),
+            2 => Ok(SignTransaction::SignWithKeychain(SignKeychain{
+                network_id: None,
+                yes: false,
+            })),
+            3 => Ok(SignTransaction::SignAlternative(SignAlternative{
+                signer_public_key: SignAlternative::input_signer_public_key()?,
+                format: SignAlternative::input_format()?,
+                output_file: SignAlternative::input_output_file()?,
+                bundle: SignAlternative::input_bundle()?,
+            })),
+            _ => unreachable!("Error")
         }
     }
 }
@@ -75,10 +115,18 @@ impl From<CliSignTransaction> for SignTransaction {
                 let privat_key = SignPrivateKey::from(cli_private_key);
                 SignTransaction::SignPrivateKey(privat_key)
             },
-            CliSignTransaction::SignAlternative(cli_key_chain) => {
-                let key_chain = SignAlternative::from(cli_key_chain);
-                SignTransaction::SignAlternative(key_chain)
-            } 
+            CliSignTransaction::SignSeedPhrase(cli_sign_seed_phrase) => {
+                let sign_seed_phrase = SignSeedPhrase::from(cli_sign_seed_phrase);
+                SignTransaction::SignSeedPhrase(sign_seed_phrase)
+            },
+            CliSignTransaction::SignWithKeychain(cli_sign_keychain) => {
+                let sign_keychain = SignKeychain::from(cli_sign_keychain);
+                SignTransaction::SignWithKeychain(sign_keychain)
+            },
+            CliSignTransaction::SignAlternative(cli_sign_alternative) => {
+                let sign_alternative = SignAlternative::from(cli_sign_alternative);
+                SignTransaction::SignAlternative(sign_alternative)
+            }
         }
     }
 }
@@ -1,59 +1,216 @@
+use near_primitives::borsh::BorshSerialize;
 use structopt::StructOpt;
-use std::str::FromStr;
-use strum_macros::{
-    Display,
-    EnumString,
-    EnumVariantNames,
-};
 use strum::VariantNames;
 use dialoguer::{
     Select,
     Input,
     theme::ColorfulTheme,
-    console::Term
 };
-
+use crate::common::PromptAnswer;
 
 
 #[derive(Debug)]
 pub struct SignAlternative {
-    pub key_chain: String,
+    pub signer_public_key: near_crypto::PublicKey,
+    pub format: crate::common::TransactionFormat,
+    pub output_file: Option<std::path::PathBuf>,
+    pub bundle: Option<std::path::PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliSignAlternative {
     #[structopt(long)]
-    key_chain: Option<String>,
+    signer_public_key: Option<crate::common::PublicKey>,
+    #[structopt(long, possible_values = crate::common::TransactionFormat::VARIANTS)]
+    encoding: Option<crate::common::TransactionFormat>,
+    #[structopt(long, parse(from_os_str))]
+    output_file: Option<std::path::PathBuf>,
+    /// Also write a transaction bundle file for an air-gapped signer
+    #[structopt(long, parse(from_os_str))]
+    bundle: Option<std::path::PathBuf>,
 }
 
 impl SignAlternative {
-    pub fn process(
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format: &str = self.format.into();
+        let mut args = vec![
+            "--signer-public-key".to_string(),
+            self.signer_public_key.to_string(),
+            "--encoding".to_string(),
+            format.to_string(),
+        ];
+        if let Some(output_file) = &self.output_file {
+            args.extend(vec!["--output-file".to_string(), output_file.to_string_lossy().into_owned()]);
+        };
+        if let Some(bundle) = &self.bundle {
+            args.extend(vec!["--bundle".to_string(), bundle.to_string_lossy().into_owned()]);
+        };
+        args
+    }
+    pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("SignAlternative process: self:       {:?}", &self);
-        println!("SignAlternative process: prepopulated_unsigned_transaction:       {:?}", &prepopulated_unsigned_transaction);
-        
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("SignAlternative process: self:       {:?}", &self);
+        log::debug!("SignAlternative process: prepopulated_unsigned_transaction:       {:?}", &prepopulated_unsigned_transaction);
+        let public_key = self.signer_public_key.clone();
+        let network_id = connection_config.as_ref().map(|connection_config| connection_config.network_id);
+        let unsigned_transaction = match connection_config {
+            None => {
+                crate::common::check_offline_public_key(&prepopulated_unsigned_transaction.public_key, &public_key)?;
+                near_primitives::transaction::Transaction {
+                    public_key,
+                    .. prepopulated_unsigned_transaction
+                }
+            },
+            Some(connection_config) => {
+                let online_signer_access_key_response = connection_config
+                    .rpc_client()
+                    .query(near_primitives::rpc::RpcQueryRequest {
+                        block_reference: near_primitives::types::Finality::Final.into(),
+                        request: near_primitives::views::QueryRequest::ViewAccessKey {
+                            account_id: prepopulated_unsigned_transaction.signer_id.clone(),
+                            public_key: public_key.clone(),
+                        },
+                    })
+                    .await
+                    .map_err(|err| {
+                        crate::common::CliError::RpcError(format!("Failed to fetch the signer access key: {:?}", err))
+                    })?;
+                let current_nonce =
+                    if let near_primitives::views::QueryResponseKind::AccessKey(online_signer_access_key) =
+                        online_signer_access_key_response.kind
+                    {
+                        online_signer_access_key.nonce
+                    } else {
+                        return Err(crate::common::CliError::RpcError(
+                            "Unexpected response to the access key query".to_string()
+                        ));
+                    };
+                near_primitives::transaction::Transaction {
+                    public_key,
+                    block_hash: online_signer_access_key_response.block_hash,
+                    nonce: current_nonce + 1,
+                    .. prepopulated_unsigned_transaction
+                }
+            }
+        };
+        let serialized_transaction = unsigned_transaction
+            .try_to_vec()
+            .expect("Transaction is not expected to fail on serialization");
+        let encoded_transaction = self.format.encode(&serialized_transaction);
+        let format: &str = self.format.into();
+        if let Some(output_file) = self.output_file {
+            std::fs::write(&output_file, &encoded_transaction)
+                .map_err(|err| {
+                    crate::common::CliError::IoError(format!("Failed to write the transaction to {}: {}", output_file.display(), err))
+                })?;
+            eprintln!("The unsigned transaction ({}) has been saved to {}", format, output_file.display());
+        };
+        if let Some(bundle) = self.bundle {
+            crate::bundle::TransactionBundle::from_transaction(network_id, &unsigned_transaction).write(&bundle)?;
+            eprintln!("The transaction bundle has been saved to {}", bundle.display());
+        };
+        crate::common::TransactionOutput {
+            transaction: &unsigned_transaction,
+            network_id,
+            encoding: self.format,
+            unsigned_transaction: Some(encoded_transaction),
+            signed_transaction: None,
+            outcome: None,
+        }
+        .print(output_format);
+        Ok(())
     }
-
-    pub fn input_key_chain() -> String {
-        Input::new()
-            .with_prompt("Enter the key chain")
+    pub fn input_signer_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+        let signer_public_key: crate::common::PublicKey = Input::new()
+            .with_prompt("Enter sender's public key (the key that will sign the transaction)")
             .interact_text()
-            .unwrap()
+            .answer()?;
+        Ok(signer_public_key.into_inner())
+    }
+    pub fn input_format() -> Result<crate::common::TransactionFormat, crate::common::CliError> {
+        eprintln!();
+        let formats = crate::common::TransactionFormat::VARIANTS;
+        let select_format = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the encoding of the unsigned transaction")
+                .items(&formats)
+                .default(0),
+        )?;
+        match select_format {
+            0 => Ok(crate::common::TransactionFormat::Base64),
+            1 => Ok(crate::common::TransactionFormat::Hex),
+            _ => unreachable!("Error")
+        }
+    }
+    pub fn input_output_file() -> Result<Option<std::path::PathBuf>, crate::common::CliError> {
+        eprintln!();
+        let choose_output = vec![
+            "Print the unsigned transaction to the terminal",
+            "Save the unsigned transaction to a file",
+        ];
+        let select_output = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Where do you want to put the unsigned transaction?")
+                .items(&choose_output)
+                .default(0),
+        )?;
+        match select_output {
+            0 => Ok(None),
+            1 => {
+                let output_file: String = Input::new()
+                    .with_prompt("Enter a path to the file")
+                    .interact_text()
+                    .answer()?;
+                Ok(Some(std::path::PathBuf::from(output_file)))
+            },
+            _ => unreachable!("Error")
+        }
+    }
+    pub fn input_bundle() -> Result<Option<std::path::PathBuf>, crate::common::CliError> {
+        eprintln!();
+        let choose_bundle = vec![
+            "No, I do not need a bundle file",
+            "Yes, I want to carry the transaction to an air-gapped signer in a bundle file",
+        ];
+        let select_bundle = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want to write a transaction bundle file?")
+                .items(&choose_bundle)
+                .default(0),
+        )?;
+        match select_bundle {
+            0 => Ok(None),
+            1 => {
+                let bundle: String = Input::new()
+                    .with_prompt("Enter a path to the bundle file")
+                    .interact_text()
+                    .answer()?;
+                Ok(Some(std::path::PathBuf::from(bundle)))
+            },
+            _ => unreachable!("Error")
+        }
     }
 }
 
 impl From<CliSignAlternative> for SignAlternative {
     fn from(item: CliSignAlternative) -> Self {
-        println!("***********.  cli sign alternative {:?}", &item);
-        let key_chain: String = match item.key_chain {
-            Some(cli_key_chain) => cli_key_chain,
-            None => SignAlternative::input_key_chain()
+        let signer_public_key: near_crypto::PublicKey = match item.signer_public_key {
+            Some(cli_signer_public_key) => cli_signer_public_key.into_inner(),
+            None => SignAlternative::input_signer_public_key().unwrap_or_else(|err| err.exit())
+        };
+        let format: crate::common::TransactionFormat = match item.encoding {
+            Some(cli_format) => cli_format,
+            None => SignAlternative::input_format().unwrap_or_else(|err| err.exit())
         };
         SignAlternative {
-            key_chain,
+            signer_public_key,
+            format,
+            output_file: item.output_file,
+            bundle: item.bundle,
         }
     }
 }
@@ -0,0 +1,88 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Password,
+};
+use crate::common::PromptAnswer;
+
+use super::sign_private_key::SignPrivateKey;
+
+
+/// Sign with the key stored for the signer in the local keychain
+/// (`~/.near-credentials/<network>/<account>.json`)
+#[derive(Debug)]
+pub struct SignKeychain {
+    /// The network of the keychain to look into; online the network the
+    /// transaction is sent to is used, offline it is asked for when omitted
+    pub network_id: Option<crate::common::NetworkId>,
+    pub yes: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignKeychain {
+    /// The keychain network (offline only, online it is the selected network)
+    #[structopt(long, possible_values = crate::common::NetworkId::VARIANTS)]
+    network: Option<crate::common::NetworkId>,
+    /// Sign without asking for a confirmation
+    #[structopt(long)]
+    yes: bool,
+}
+
+impl From<CliSignKeychain> for SignKeychain {
+    fn from(item: CliSignKeychain) -> Self {
+        SignKeychain {
+            network_id: item.network,
+            yes: item.yes,
+        }
+    }
+}
+
+impl SignKeychain {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(network_id) = self.network_id {
+            args.extend(vec!["--network".to_string(), network_id.to_string()]);
+        };
+        if self.yes {
+            args.push("--yes".to_string());
+        };
+        args
+    }
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        let network_id = match (&connection_config, self.network_id) {
+            (Some(connection_config), Some(network_id)) if network_id != connection_config.network_id => {
+                return Err(crate::common::CliError::SigningError(format!(
+                    "The key from the {} keychain cannot sign a transaction which is sent to {}",
+                    network_id,
+                    connection_config.network_id
+                )));
+            },
+            (Some(connection_config), _) => connection_config.network_id,
+            (None, Some(network_id)) => network_id,
+            (None, None) => crate::common::input_network_id()?
+        };
+        let signer_secret_key = crate::keystore::load_secret_key(
+            network_id,
+            &prepopulated_unsigned_transaction.signer_id,
+            SignKeychain::input_passphrase,
+        )?;
+        SignPrivateKey {
+            signer_secret_key,
+            yes: self.yes,
+        }
+        .process(prepopulated_unsigned_transaction, connection_config, output_format)
+        .await
+    }
+    pub fn input_passphrase() -> Result<zeroize::Zeroizing<String>, crate::common::CliError> {
+        Password::new()
+            .with_prompt("Enter the passphrase of the encrypted key")
+            .interact()
+            .map(zeroize::Zeroizing::new)
+            .answer()
+    }
+}
@@ -1,132 +1,172 @@
 use near_primitives::borsh::BorshSerialize;
-use std::str::FromStr;
 use structopt::StructOpt;
-use dialoguer::{
-    Input,
-};
 
 
-#[derive(Debug)]
 pub struct SignPrivateKey {
-    pub signer_public_key: String,
-    pub signer_secret_key: String,
+    pub signer_secret_key: crate::common::SecretKey,
+    pub yes: bool,
+}
+
+impl std::fmt::Debug for SignPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignPrivateKey")
+            .field("signer_public_key", &self.signer_secret_key.public_key())
+            .field("signer_secret_key", &crate::common::REDACTED_SECRET)
+            .field("yes", &self.yes)
+            .finish()
+    }
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliSignPrivateKey {
+    /// Optional, it is derived from the secret key and only checked against it
     #[structopt(long)]
-    signer_public_key: Option<String>,
+    signer_public_key: Option<crate::common::PublicKey>,
     #[structopt(long)]
-    signer_secret_key: Option<String>
+    signer_secret_key: Option<crate::common::SecretKey>,
+    /// Sign without asking for a confirmation
+    #[structopt(long)]
+    yes: bool,
 }
 
 impl SignPrivateKey {
-    fn rpc_client(self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--signer-public-key".to_string(),
+            self.signer_secret_key.public_key().to_string(),
+            "--signer-secret-key".to_string(),
+            crate::common::REDACTED_SECRET.to_string(),
+        ];
+        if self.yes {
+            args.push("--yes".to_string());
+        };
+        args
     }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("SignPrivateKey process: self:\n       {:?}", &self);
-        println!("SignPrivateKey process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        println!("SignPrivateKey process: selected_server_url:\n       {:?}", &selected_server_url);
-        let public_key = near_crypto::PublicKey::from_str(&self.signer_public_key).unwrap();
-        let signer_secret_key = near_crypto::SecretKey::from_str(&self.signer_secret_key).unwrap();
-        if selected_server_url.is_empty() {
-            let unsigned_transaction = near_primitives::transaction::Transaction {
-                public_key,
-                .. prepopulated_unsigned_transaction
-            };
-            let signature = signer_secret_key
-                .sign(unsigned_transaction.get_hash().as_ref());
-            let signed_transaction =
-                near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
-            let serialize_to_base64 = near_primitives::serialize::to_base64(
-                    signed_transaction
-                        .try_to_vec()
-                        .expect("Transaction is not expected to fail on serialization"),
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("SignPrivateKey process: self:\n       {:?}", &self);
+        log::debug!("SignPrivateKey process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        log::debug!("SignPrivateKey process: connection_config:\n       {:?}", &connection_config);
+        let public_key = self.signer_secret_key.public_key();
+        let network_id = connection_config.as_ref().map(|connection_config| connection_config.network_id);
+        let unsigned_transaction = match &connection_config {
+            None => {
+                crate::common::check_offline_public_key(&prepopulated_unsigned_transaction.public_key, &public_key)?;
+                near_primitives::transaction::Transaction {
+                    public_key,
+                    .. prepopulated_unsigned_transaction
+                }
+            },
+            Some(connection_config) => {
+                log::info!(
+                    "Fetching the nonce of {} for {} from {}",
+                    public_key,
+                    prepopulated_unsigned_transaction.signer_id,
+                    connection_config.url
                 );
-            println!("---  serialize_to_base64:   --- \n   {:#?}", &serialize_to_base64)
+                let online_signer_access_key_response = connection_config
+                    .rpc_client()
+                    .query(near_primitives::rpc::RpcQueryRequest {
+                        block_reference: near_primitives::types::Finality::Final.into(),
+                        request: near_primitives::views::QueryRequest::ViewAccessKey {
+                            account_id: prepopulated_unsigned_transaction.signer_id.clone(),
+                            public_key: public_key.clone(),
+                        },
+                    })
+                    .await
+                    .map_err(|err| {
+                        crate::common::CliError::RpcError(format!("Failed to fetch the signer access key: {:?}", err))
+                    })?;
+                log::debug!("online_signer_access_key_response:\n   {:?}", &online_signer_access_key_response);
+                let current_nonce =
+                    if let near_primitives::views::QueryResponseKind::AccessKey(online_signer_access_key) =
+                        online_signer_access_key_response.kind
+                    {
+                        online_signer_access_key.nonce
+                    } else {
+                        return Err(crate::common::CliError::RpcError(
+                            "Unexpected response to the access key query".to_string()
+                        ));
+                    };
+                log::debug!("current_nonce:  {:?}", &current_nonce);
+                near_primitives::transaction::Transaction {
+                    public_key,
+                    block_hash: online_signer_access_key_response.block_hash,
+                    nonce: current_nonce + 1,
+                    .. prepopulated_unsigned_transaction
+                }
             }
-        else {
-            let online_signer_access_key_response = self
-                .rpc_client(&selected_server_url)
-                .query(near_primitives::rpc::RpcQueryRequest {
-                    block_reference: near_primitives::types::Finality::Final.into(),
-                    request: near_primitives::views::QueryRequest::ViewAccessKey {
-                        account_id: prepopulated_unsigned_transaction.signer_id.clone(),
-                        public_key: public_key.clone(),
-                    },
-                })
-                .await
-                .map_err(|err| {
-                    println!("Error online_signer_access_key_response:   {:?}", &err)
-                }).unwrap();
-            println!("online_signer_access_key_response:\n   {:?}", &online_signer_access_key_response);
-            let current_nonce =
-                if let near_primitives::views::QueryResponseKind::AccessKey(online_signer_access_key) =
-                    online_signer_access_key_response.kind
-                {
-                    online_signer_access_key.nonce
-                } else {
-                    return println!("Error current_nonce");
-                };
-            println!("current_nonce:  {:?}", &current_nonce);
-            let unsigned_transaction = near_primitives::transaction::Transaction {
-                public_key,
-                block_hash: online_signer_access_key_response.block_hash,
-                nonce: current_nonce + 1,
-                .. prepopulated_unsigned_transaction
-            };
-            println!("unsigned_transaction:  {:#?}", &unsigned_transaction);
-            let signature = signer_secret_key
-                .sign(unsigned_transaction.get_hash().as_ref());
-            let signed_transaction =
-                near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
-            println!("---  Signed transaction:   ---    {:#?}", &signed_transaction);
-            let transaction_info = near_jsonrpc_client::new_client(&selected_server_url)
-                .broadcast_tx_commit(near_primitives::serialize::to_base64(
-                    signed_transaction
-                        .try_to_vec()
-                        .expect("Transaction is not expected to fail on serialization"),
-                ))
-                .await
-                .map_err(|err| {
-                    println!("Error transaction:  {:?}",&err)
-                })
-                .unwrap();
-            println!("Success: {:#?}", transaction_info);
+        };
+        eprintln!();
+        crate::common::print_transaction_summary(&unsigned_transaction, network_id);
+        eprintln!();
+        if !self.yes && !crate::common::confirm_signing()? {
+            return Err(crate::common::CliError::UserCancelled);
+        };
+        let signature = self.signer_secret_key
+            .sign(unsigned_transaction.get_hash().as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        let serialize_to_base64 = near_primitives::serialize::to_base64(
+            signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        );
+        let outcome = match connection_config {
+            None => None,
+            Some(connection_config) => {
+                log::debug!("---  Signed transaction:   ---    {:#?}", &signed_transaction);
+                log::info!("Broadcasting the transaction to {}", connection_config.url);
+                let transaction_info = connection_config
+                    .rpc_client()
+                    .broadcast_tx_commit(serialize_to_base64.clone())
+                    .await
+                    .map_err(|err| {
+                        crate::common::CliError::RpcError(format!("Failed to broadcast the transaction: {:?}", err))
+                    })?;
+                Some(transaction_info)
             }
+        };
+        let transaction_output = crate::common::TransactionOutput {
+            transaction: &signed_transaction.transaction,
+            network_id,
+            encoding: crate::common::TransactionFormat::Base64,
+            unsigned_transaction: None,
+            signed_transaction: Some(serialize_to_base64),
+            outcome,
+        };
+        transaction_output.print(output_format);
+        transaction_output.status()
     }
-    pub fn signer_public_key() -> String {
-        Input::new()
-            .with_prompt("enter sender's public key")
-            .interact_text()
-            .unwrap()
-    }
-    pub fn signer_secret_key() -> String {
-        Input::new()
-            .with_prompt("enter sender's private key")
-            .interact_text()
-            .unwrap()
+    pub fn signer_secret_key() -> Result<crate::common::SecretKey, crate::common::CliError> {
+        crate::common::input_secret("enter sender's private key")
     }
 }
 
 impl From<CliSignPrivateKey> for SignPrivateKey {
     fn from(item: CliSignPrivateKey) -> Self {
-        let signer_public_key: String = match item.signer_public_key {
-            Some(cli_public_key) => cli_public_key,
-            None => SignPrivateKey::signer_public_key()
-        };
-        let signer_secret_key: String = match item.signer_secret_key {
+        let signer_secret_key: crate::common::SecretKey = match item.signer_secret_key {
             Some(cli_secret_key) => cli_secret_key,
-            None => SignPrivateKey::signer_secret_key()
+            None => SignPrivateKey::signer_secret_key().unwrap_or_else(|err| err.exit())
+        };
+        if let Some(cli_public_key) = item.signer_public_key {
+            let cli_public_key = cli_public_key.into_inner();
+            if cli_public_key != signer_secret_key.public_key() {
+                crate::common::CliError::ParseError(format!(
+                    "The signer public key {} does not match the secret key (its public key is {})",
+                    cli_public_key,
+                    signer_secret_key.public_key()
+                ))
+                .exit()
+            };
         };
         SignPrivateKey {
-            signer_public_key,
-            signer_secret_key
+            signer_secret_key,
+            yes: item.yes,
         }
     }
 }
@@ -0,0 +1,81 @@
+use structopt::StructOpt;
+
+use super::sign_private_key::SignPrivateKey;
+
+
+pub struct SignSeedPhrase {
+    pub seed_phrase: crate::common::SeedPhrase,
+    pub seed_phrase_hd_path: slip10::BIP32Path,
+    pub yes: bool,
+}
+
+impl std::fmt::Debug for SignSeedPhrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignSeedPhrase")
+            .field("seed_phrase", &crate::common::REDACTED_SECRET)
+            .field("seed_phrase_hd_path", &crate::common::bip32path_to_string(&self.seed_phrase_hd_path))
+            .field("yes", &self.yes)
+            .finish()
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignSeedPhrase {
+    #[structopt(long)]
+    seed_phrase: Option<crate::common::SeedPhrase>,
+    #[structopt(long, default_value = "m/44'/397'/0'")]
+    seed_phrase_hd_path: slip10::BIP32Path,
+    /// Sign without asking for a confirmation
+    #[structopt(long)]
+    yes: bool,
+}
+
+impl SignSeedPhrase {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--seed-phrase".to_string(),
+            crate::common::REDACTED_SECRET.to_string(),
+            "--seed-phrase-hd-path".to_string(),
+            crate::common::bip32path_to_string(&self.seed_phrase_hd_path),
+        ];
+        if self.yes {
+            args.push("--yes".to_string());
+        };
+        args
+    }
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        let signer_secret_key = crate::common::get_secret_key_from_seed_phrase(
+            self.seed_phrase.as_str(),
+            &self.seed_phrase_hd_path,
+        )
+        .map_err(crate::common::CliError::SigningError)?;
+        SignPrivateKey {
+            signer_secret_key,
+            yes: self.yes,
+        }
+        .process(prepopulated_unsigned_transaction, connection_config, output_format)
+        .await
+    }
+    pub fn input_seed_phrase() -> Result<crate::common::SeedPhrase, crate::common::CliError> {
+        crate::common::input_secret("Enter the seed phrase")
+    }
+}
+
+impl From<CliSignSeedPhrase> for SignSeedPhrase {
+    fn from(item: CliSignSeedPhrase) -> Self {
+        let seed_phrase: crate::common::SeedPhrase = match item.seed_phrase {
+            Some(cli_seed_phrase) => cli_seed_phrase,
+            None => SignSeedPhrase::input_seed_phrase().unwrap_or_else(|err| err.exit())
+        };
+        SignSeedPhrase {
+            seed_phrase,
+            seed_phrase_hd_path: item.seed_phrase_hd_path,
+            yes: item.yes,
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use structopt::StructOpt;
-use std::str::FromStr;
 use async_recursion::async_recursion;
 
 use crate::command::on_off_line_mode::server::sender::receiver::{
@@ -26,7 +25,7 @@ impl From<CliFullAccessType> for FullAccessType {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         FullAccessType {
             next_action,
@@ -35,17 +34,20 @@ impl From<CliFullAccessType> for FullAccessType {
 }
 
 impl FullAccessType {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        self.next_action.to_cli_skip_args()
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         nonce: near_primitives::types::Nonce,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-        public_key_string: String,
-    ) {
-        println!("FullAccessType process: self:\n       {:?}", &self);
-        println!("FullAccessType process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        let public_key = near_crypto::PublicKey::from_str(&public_key_string).unwrap();
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+        public_key: near_crypto::PublicKey,
+    ) -> crate::common::CliResult {
+        log::debug!("FullAccessType process: self:\n       {:?}", &self);
+        log::debug!("FullAccessType process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
                 nonce,
                 permission: near_primitives::account::AccessKeyPermission::FullAccess
@@ -63,15 +65,15 @@ impl FullAccessType {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, selected_server_url, public_key_string).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
     }
 }
@@ -1,13 +1,11 @@
 use structopt::StructOpt;
-use std::{str::FromStr, vec};
-use std::num::ParseIntError;
 
 use dialoguer::{
     Select,
     Input,
-    theme::ColorfulTheme,
-    console::Term
+    theme::ColorfulTheme
 };
+use crate::common::PromptAnswer;
 use async_recursion::async_recursion;
 
 
@@ -15,6 +13,7 @@ use crate::command::on_off_line_mode::server::sender::receiver::{
     ActionSubcommand,
     CliActionSkipSubcommand
 };
+use crate::common::NearBalance;
 
 
 #[derive(Debug)]
@@ -29,8 +28,11 @@ pub struct FunctionCallType {
 pub struct  CliFunctionCallType {
     #[structopt(long)]
     allowance: Option<NearBalance>,
+    /// The access key may spend any amount on gas and transaction fees
+    #[structopt(long, conflicts_with = "allowance")]
+    no_allowance: bool,
     #[structopt(long)]
-    receiver_id: Option<near_primitives::types::AccountId>,
+    receiver_id: Option<crate::common::ValidAccountId>,
     #[structopt(long)]
     method_names: Option<String>,
     #[structopt(subcommand)]
@@ -39,18 +41,14 @@ pub struct  CliFunctionCallType {
 
 impl From<CliFunctionCallType> for FunctionCallType {
     fn from(item: CliFunctionCallType) -> Self {
-        let allowance: Option<near_primitives::types::Balance> = match item.allowance {
-            Some(cli_allowance) => {
-                let allowance = match cli_allowance {
-                    NearBalance(num) => num
-                };
-                Some(allowance)
-            },
-            None => FunctionCallType::input_allowance()
+        let allowance: Option<near_primitives::types::Balance> = match (item.allowance, item.no_allowance) {
+            (Some(cli_allowance), _) => Some(cli_allowance.0),
+            (None, true) => None,
+            (None, false) => FunctionCallType::input_allowance().unwrap_or_else(|err| err.exit())
         }; 
         let receiver_id: near_primitives::types::AccountId = match item.receiver_id {
-            Some(cli_receiver_id) => near_primitives::types::AccountId::from(cli_receiver_id),
-            None => FunctionCallType::input_receiver_id()
+            Some(cli_receiver_id) => cli_receiver_id.into_inner(),
+            None => FunctionCallType::input_receiver_id().unwrap_or_else(|err| err.exit())
         }; 
         let method_names: Vec<String> = match item.method_names {
             Some(cli_method_names) => {
@@ -60,13 +58,13 @@ impl From<CliFunctionCallType> for FunctionCallType {
                     cli_method_names.split(',').map(String::from).collect::<Vec<String>>()
                 }
             },
-            None => FunctionCallType::input_method_names()
+            None => FunctionCallType::input_method_names().unwrap_or_else(|err| err.exit())
         }; 
         let next_action: Box<ActionSubcommand> = match item.next_action {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         FunctionCallType {
             allowance,
@@ -78,17 +76,31 @@ impl From<CliFunctionCallType> for FunctionCallType {
 }
 
 impl FunctionCallType {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = match self.allowance {
+            Some(allowance) => vec!["--allowance".to_string(), NearBalance(allowance).to_string()],
+            None => vec!["--no-allowance".to_string()],
+        };
+        args.extend(vec![
+            "--receiver-id".to_string(),
+            self.receiver_id.clone(),
+            "--method-names".to_string(),
+            self.method_names.join(","),
+        ]);
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         nonce: near_primitives::types::Nonce,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-        public_key_string: String,
-    ) {
-        println!("FunctionCallType process: self:\n       {:?}", &self);
-        println!("FunctionCallType process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        let public_key = near_crypto::PublicKey::from_str(&public_key_string).unwrap();
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+        public_key: near_crypto::PublicKey,
+    ) -> crate::common::CliResult {
+        log::debug!("FunctionCallType process: self:\n       {:?}", &self);
+        log::debug!("FunctionCallType process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
                 nonce,
                 permission: near_primitives::account::AccessKeyPermission::FunctionCall(
@@ -112,103 +124,77 @@ impl FunctionCallType {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, selected_server_url, public_key_string).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
     }
-    pub fn input_method_names() -> Vec<String> {
-        println!();
+    pub fn input_method_names() -> Result<Vec<String>, crate::common::CliError> {
+        eprintln!();
         let choose_input = vec![
             "Yes, I want to input a list of method names that can be used",
             "No, I don't to input a list of method names that can be used"
         ];
-        let select_choose_input = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Do You want to input a list of method names that can be used")
-            .items(&choose_input)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let select_choose_input = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do You want to input a list of method names that can be used")
+                .items(&choose_input)
+                .default(0),
+        )?;
         match select_choose_input {
-            Some(0) => {
+            0 => {
                 let mut input_method_names: String = Input::new()
                     .with_prompt("Enter a list of method names that can be used. The access key only allows transactions with the function call of one of the given method names. Empty list means any method name can be used.")
                     .interact_text()
-                    .unwrap();
+                    .answer()?;
                 if input_method_names.contains("\"") {input_method_names.clear()};
                 if input_method_names.is_empty() {
-                    vec![]
+                    Ok(vec![])
                 } else {
-                    input_method_names.split(',').map(String::from).collect::<Vec<String>>()
+                    Ok(input_method_names.split(',').map(String::from).collect::<Vec<String>>())
                 }
             },
-            Some(1) => vec![],
+            1 => Ok(vec![]),
             _ => unreachable!("Error")
         }
     }
-    pub fn input_allowance() -> Option<near_primitives::types::Balance> {
-        println!();
+    pub fn input_allowance() -> Result<Option<near_primitives::types::Balance>, crate::common::CliError> {
+        eprintln!();
         let choose_input = vec![
             "Yes, I want to input allowance for receiver ID",
             "No, I don't to input allowance for receiver ID"
         ];
-        let select_choose_input = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Do You want to input an allowance for receiver ID")
-            .items(&choose_input)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let select_choose_input = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do You want to input an allowance for receiver ID")
+                .items(&choose_input)
+                .default(0),
+        )?;
         match select_choose_input {
-            Some(0) => {
-                let input: String = Input::new()
-                    .with_prompt("Enter an allowance which is a balance limit to use by this access key to pay for function call gas and transaction fees.")
+            0 => {
+                let allowance: NearBalance = Input::new()
+                    .with_prompt("Enter an allowance which is a balance limit to use by this access key to pay for function call gas and transaction fees. (example: 0.25 NEAR)")
                     .interact_text()
-                    .unwrap();
-                let allowance_near_balance: NearBalance = NearBalance::from_str(&input).unwrap();
-                let allowance = match allowance_near_balance {
-                    NearBalance(num) => num
-                };
-                Some(allowance)
+                    .answer()?;
+                Ok(Some(allowance.0))
             },
-            Some(1) => None,
+            1 => Ok(None),
             _ => unreachable!("Error")
         }
         
     }
-    pub fn input_receiver_id() -> near_primitives::types::AccountId {
-        println!();
-        Input::new()
+    pub fn input_receiver_id() -> Result<near_primitives::types::AccountId, crate::common::CliError> {
+        eprintln!();
+        let receiver_id: crate::common::ValidAccountId = Input::new()
             .with_prompt("Enter a receiver to use by this access key to pay for function call gas and transaction fees.")
             .interact_text()
-            .unwrap()
-    }
-}
-
-#[derive(Debug)]
-pub struct NearBalance (u128);
-
-impl FromStr for NearBalance {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let number: u128 = s.parse().unwrap_or_else(|ParseIntError| -> u128 {
-            let mut s: String = s.to_string().clone();
-            s.make_ascii_uppercase();
-            match s.contains("NEAR") {
-                true => {
-                    let num:u128 = s.trim_matches(char::is_alphabetic)
-                        .parse()
-                        .unwrap();
-                    num * 10u128.pow(24)
-                },
-                _ => 0
-            }
-        });
-        Ok(NearBalance(number))
+            .answer()?;
+        Ok(receiver_id.into_inner())
     }
 }
@@ -6,9 +6,9 @@ use strum::VariantNames;
 use dialoguer::{
     Select,
     Input,
-    theme::ColorfulTheme,
-    console::Term
+    theme::ColorfulTheme
 };
+use crate::common::PromptAnswer;
 use async_recursion::async_recursion;
 
 
@@ -29,14 +29,14 @@ use full_access_type::{
 
 #[derive(Debug)]
 pub struct AddAccessKeyAction {
-    pub public_key: String,
+    pub public_key: near_crypto::PublicKey,
     pub nonce: near_primitives::types::Nonce,
     pub permission: AccessKeyPermission
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliAddAccessKeyAction {
-    public_key: Option<String>,
+    public_key: Option<crate::common::PublicKey>,
     #[structopt(long)]
     nonce: Option<u64>,
     #[structopt(subcommand)]
@@ -57,19 +57,19 @@ pub enum AccessKeyPermission {
 
 impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
     fn from(item: CliAddAccessKeyAction) -> Self {
-        let public_key: near_primitives::types::AccountId = match item.public_key {
-            Some(cli_public_key) => near_primitives::types::AccountId::from(cli_public_key),
-            None => AddAccessKeyAction::input_public_key()
+        let public_key: near_crypto::PublicKey = match item.public_key {
+            Some(cli_public_key) => cli_public_key.into_inner(),
+            None => AddAccessKeyAction::input_public_key().unwrap_or_else(|err| err.exit())
         };
         let nonce: near_primitives::types::Nonce = match item.nonce {
             Some(cli_nonce) => near_primitives::types::Nonce::from(cli_nonce),
-            None => AddAccessKeyAction::input_nonce()
+            None => AddAccessKeyAction::input_nonce().unwrap_or_else(|err| err.exit())
         };
         let permission: AccessKeyPermission = match item.permission {
             Some(cli_permission) => {
                 AccessKeyPermission::from(cli_permission)
             },
-            None => AccessKeyPermission::choose_permission()
+            None => AccessKeyPermission::choose_permission().unwrap_or_else(|err| err.exit())
         };
         AddAccessKeyAction {
             public_key,
@@ -80,32 +80,42 @@ impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
 }
 
 impl AddAccessKeyAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            self.public_key.to_string(),
+            "--nonce".to_string(),
+            self.nonce.to_string(),
+        ];
+        args.extend(self.permission.to_cli_args()?);
+        Some(args)
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-        _public_key_string: String,
-    ) {
-        println!("AddAccessKeyAction process: self:\n       {:?}", &self);
-        println!("AddAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("AddAccessKeyAction process: self:\n       {:?}", &self);
+        log::debug!("AddAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         match self.permission {
-            AccessKeyPermission::FullAccess(full_access_type) => full_access_type.process(self.nonce, prepopulated_unsigned_transaction, selected_server_url, self.public_key).await,
-            AccessKeyPermission::FunctionCall(function_call_type) => function_call_type.process(self.nonce, prepopulated_unsigned_transaction, selected_server_url, self.public_key).await,
+            AccessKeyPermission::FullAccess(full_access_type) => full_access_type.process(self.nonce, prepopulated_unsigned_transaction, connection_config, output_format, self.public_key).await,
+            AccessKeyPermission::FunctionCall(function_call_type) => function_call_type.process(self.nonce, prepopulated_unsigned_transaction, connection_config, output_format, self.public_key).await,
         }
     }
-    pub fn input_nonce() -> near_primitives::types::Nonce {
+    pub fn input_nonce() -> Result<near_primitives::types::Nonce, crate::common::CliError> {
             Input::new()
                 .with_prompt("Enter the nonce for this access key")
                 .interact_text()
-                .unwrap()
+                .answer()
             
     }
-    pub fn input_public_key() -> String {
-            Input::new()
+    pub fn input_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+            let public_key: crate::common::PublicKey = Input::new()
                 .with_prompt("Enter a public key for this access key")
                 .interact_text()
-                .unwrap()
+                .answer()?;
+            Ok(public_key.into_inner())
     }
 }
 
@@ -127,31 +137,38 @@ impl From<CliAccessKeyPermission> for AccessKeyPermission {
 
 
 impl AccessKeyPermission {
-    pub fn choose_permission() -> Self {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (subcommand, args) = match self {
+            AccessKeyPermission::FunctionCall(function_call_type) => ("function-call", function_call_type.to_cli_args()?),
+            AccessKeyPermission::FullAccess(full_access_type) => ("full-access", full_access_type.to_cli_args()?),
+        };
+        Some(std::iter::once(subcommand.to_string()).chain(args).collect())
+    }
+    pub fn choose_permission() -> Result<Self, crate::common::CliError> {
         let permissions = AccessKeyPermission::VARIANTS;
-        let select_permission = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select a permission that you want to add to the access key:")
-            .items(&permissions)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let select_permission = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a permission that you want to add to the access key:")
+                .items(&permissions)
+                .default(0),
+        )?;
         match select_permission {
-            Some(0) => {
-                let allowance: Option<near_primitives::types::Balance> = FunctionCallType::input_allowance();
-                let receiver_id: near_primitives::types::AccountId = FunctionCallType::input_receiver_id();
-                let method_names: Vec<String> = FunctionCallType::input_method_names();
-                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command());
-                AccessKeyPermission::FunctionCall(
+            0 => {
+                let allowance: Option<near_primitives::types::Balance> = FunctionCallType::input_allowance()?;
+                let receiver_id: near_primitives::types::AccountId = FunctionCallType::input_receiver_id()?;
+                let method_names: Vec<String> = FunctionCallType::input_method_names()?;
+                let next_action: Box<ActionSubcommand> = Box::new(ActionSubcommand::choose_action_command()?);
+                Ok(AccessKeyPermission::FunctionCall(
                     FunctionCallType {
                         allowance,
                         receiver_id,
                         method_names,
                         next_action
-                })
+                }))
             },
-            Some(1) => AccessKeyPermission::FullAccess(FullAccessType {
-                next_action: Box::new(ActionSubcommand::choose_action_command())
-            }),
+            1 => Ok(AccessKeyPermission::FullAccess(FullAccessType {
+                next_action: Box::new(ActionSubcommand::choose_action_command()?)
+            })),
             _ => unreachable!("Error")
         }
     }
@@ -1,27 +1,206 @@
-// use structopt::StructOpt;
-// use std::str::FromStr;
-// use strum_macros::{
-//     Display,
-//     EnumString,
-//     EnumVariantNames,
-// };
-// use strum::VariantNames;
-// use dialoguer::{
-//     Select,
-//     Input,
-//     theme::ColorfulTheme,
-//     console::Term
-// };
-// use super::super::{
-//     ActionSubcommand,
-// };
+use structopt::StructOpt;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme
+};
+use crate::common::PromptAnswer;
+use async_recursion::async_recursion;
 
-// #[derive(Debug, StructOpt)]
-// pub struct CallFunction {
-//     method_name: String,
-//     args: String,
-//     gas: u64,  // default 1000000000
-//     deposit: u128,  // default 0
-//     #[structopt(subcommand)]
-//     next_action: Box<ActionSubcommand>
-// }
+use super::super::{
+    ActionSubcommand,
+    CliActionSkipSubcommand
+};
+use crate::common::NearBalance;
+
+
+const DEFAULT_FUNCTION_CALL_GAS: near_primitives::types::Gas = 100_000_000_000_000;
+
+#[derive(Debug)]
+pub struct CallFunctionAction {
+    pub method_name: String,
+    pub args: Vec<u8>,
+    pub gas: near_primitives::types::Gas,
+    pub deposit: near_primitives::types::Balance,
+    pub next_action: Box<ActionSubcommand>
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCallFunctionAction {
+    method_name: Option<String>,
+    #[structopt(long)]
+    args: Option<String>,
+    #[structopt(long, conflicts_with = "args")]
+    args_base64: Option<String>,
+    #[structopt(long)]
+    gas: Option<near_primitives::types::Gas>,
+    #[structopt(long)]
+    deposit: Option<NearBalance>,
+    #[structopt(subcommand)]
+    next_action: Option<CliActionSkipSubcommand>
+}
+
+impl From<CliCallFunctionAction> for CallFunctionAction {
+    fn from(item: CliCallFunctionAction) -> Self {
+        let method_name: String = match item.method_name {
+            Some(cli_method_name) => cli_method_name,
+            None => CallFunctionAction::input_method_name().unwrap_or_else(|err| err.exit())
+        };
+        let args: Vec<u8> = match (item.args, item.args_base64) {
+            (Some(cli_args), _) => CallFunctionAction::parse_json_args(&cli_args)
+                .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit()),
+            (None, Some(cli_args_base64)) => CallFunctionAction::parse_base64_args(&cli_args_base64)
+                .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit()),
+            (None, None) => CallFunctionAction::input_args().unwrap_or_else(|err| err.exit())
+        };
+        let gas: near_primitives::types::Gas = match item.gas {
+            Some(cli_gas) => cli_gas,
+            None => CallFunctionAction::input_gas().unwrap_or_else(|err| err.exit())
+        };
+        let deposit: near_primitives::types::Balance = match item.deposit {
+            Some(cli_deposit) => cli_deposit.0,
+            None => CallFunctionAction::input_deposit().unwrap_or_else(|err| err.exit())
+        };
+        let next_action: Box<ActionSubcommand> = match item.next_action {
+            Some(cli_skip_action) => {
+                Box::new(ActionSubcommand::from(cli_skip_action))
+            },
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
+        };
+        CallFunctionAction {
+            method_name,
+            args,
+            gas,
+            deposit,
+            next_action
+        }
+    }
+}
+
+impl CallFunctionAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.method_name.clone()];
+        match std::str::from_utf8(&self.args) {
+            Ok(json_args) if !self.args.is_empty() && CallFunctionAction::parse_json_args(json_args).is_ok() => {
+                args.extend(vec!["--args".to_string(), json_args.to_string()]);
+            },
+            _ => {
+                args.extend(vec!["--args-base64".to_string(), near_primitives::serialize::to_base64(&self.args)]);
+            }
+        };
+        args.extend(vec![
+            "--gas".to_string(),
+            self.gas.to_string(),
+            "--deposit".to_string(),
+            NearBalance(self.deposit).to_string(),
+        ]);
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("CallFunctionAction process: self:\n       {:?}", &self);
+        log::debug!("CallFunctionAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        let action = near_primitives::transaction::Action::FunctionCall(
+            near_primitives::transaction::FunctionCallAction {
+                method_name: self.method_name.clone(),
+                args: self.args.clone(),
+                gas: self.gas,
+                deposit: self.deposit,
+            }
+        );
+        let mut actions= prepopulated_unsigned_transaction.actions.clone();
+        actions.push(action);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            .. prepopulated_unsigned_transaction
+        };
+        match *self.next_action {
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
+        }
+    }
+    pub fn input_method_name() -> Result<String, crate::common::CliError> {
+        eprintln!();
+        Input::new()
+            .with_prompt("Enter a method name of the contract to call")
+            .interact_text()
+            .answer()
+    }
+    pub fn input_args() -> Result<Vec<u8>, crate::common::CliError> {
+        eprintln!();
+        let choose_input = vec![
+            "JSON (example: {\"account_id\": \"bob.testnet\"})",
+            "Base64-encoded binary",
+            "No arguments",
+        ];
+        let select_choose_input = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("In which format do you want to enter the function arguments?")
+                .items(&choose_input)
+                .default(0),
+        )?;
+        match select_choose_input {
+            0 => loop {
+                let input: String = Input::new()
+                    .with_prompt("Enter arguments for the function as JSON")
+                    .interact_text()
+                    .answer()?;
+                match CallFunctionAction::parse_json_args(&input) {
+                    Ok(args) => break Ok(args),
+                    Err(err) => eprintln!("{}", err)
+                }
+            },
+            1 => loop {
+                let input: String = Input::new()
+                    .with_prompt("Enter arguments for the function as base64")
+                    .interact_text()
+                    .answer()?;
+                match CallFunctionAction::parse_base64_args(&input) {
+                    Ok(args) => break Ok(args),
+                    Err(err) => eprintln!("{}", err)
+                }
+            },
+            2 => Ok(vec![]),
+            _ => unreachable!("Error")
+        }
+    }
+    pub fn input_gas() -> Result<near_primitives::types::Gas, crate::common::CliError> {
+        eprintln!();
+        Input::new()
+            .with_prompt("Enter the amount of gas to attach to the function call")
+            .default(DEFAULT_FUNCTION_CALL_GAS)
+            .interact_text()
+            .answer()
+    }
+    pub fn input_deposit() -> Result<near_primitives::types::Balance, crate::common::CliError> {
+        eprintln!();
+        let deposit: NearBalance = Input::new()
+            .with_prompt("How many NEAR Tokens do you want to attach to the function call? (example: 10 NEAR or 0.5 NEAR)")
+            .default(NearBalance(0))
+            .interact_text()
+            .answer()?;
+        Ok(deposit.0)
+    }
+    fn parse_json_args(input: &str) -> Result<Vec<u8>, String> {
+        serde_json::from_str::<serde_json::Value>(input)
+            .map_err(|err| format!("Function arguments are not a valid JSON: {}", err))?;
+        Ok(input.as_bytes().to_vec())
+    }
+    fn parse_base64_args(input: &str) -> Result<Vec<u8>, String> {
+        near_primitives::serialize::from_base64(input)
+            .map_err(|err| format!("Function arguments are not a valid base64: {}", err))
+    }
+}
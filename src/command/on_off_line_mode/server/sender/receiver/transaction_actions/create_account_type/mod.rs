@@ -24,7 +24,7 @@ impl From<CliCreateAccountAction> for CreateAccountAction {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         CreateAccountAction {
             next_action
@@ -33,15 +33,19 @@ impl From<CliCreateAccountAction> for CreateAccountAction {
 }
 
 impl CreateAccountAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        self.next_action.to_cli_skip_args()
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
         // public_key_string: String,
-    ) {
-        println!("CreateAccountAction process: self:\n       {:?}", &self);
-        println!("CreateAccountAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+    ) -> crate::common::CliResult {
+        log::debug!("CreateAccountAction process: self:\n       {:?}", &self);
+        log::debug!("CreateAccountAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         let action = near_primitives::transaction::Action::CreateAccount(
             near_primitives::transaction::CreateAccountAction {}
         );
@@ -52,15 +56,15 @@ impl CreateAccountAction {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, selected_server_url, "".to_string()).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
     }
 }
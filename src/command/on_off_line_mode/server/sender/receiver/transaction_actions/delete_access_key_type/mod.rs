@@ -1,8 +1,8 @@
 use structopt::StructOpt;
-use std::str::FromStr;
 use dialoguer::{
     Input,
 };
+use crate::common::PromptAnswer;
 use async_recursion::async_recursion;
 
 use super::super::{
@@ -13,29 +13,29 @@ use super::super::{
 
 #[derive(Debug)]
 pub struct DeleteAccessKeyAction {
-    pub public_key: String,
+    pub public_key: near_crypto::PublicKey,
     pub next_action: Box<ActionSubcommand>
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliDeleteAccessKeyAction {
     #[structopt(long)]
-    public_key: Option<String>,
+    public_key: Option<crate::common::PublicKey>,
     #[structopt(subcommand)]
     next_action: Option<CliActionSkipSubcommand>
 }
 
 impl From<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
     fn from(item: CliDeleteAccessKeyAction) -> Self {
-        let public_key: String = match item.public_key {
-            Some(cli_public_key) => cli_public_key,
-            None => DeleteAccessKeyAction::input_public_key()
+        let public_key: near_crypto::PublicKey = match item.public_key {
+            Some(cli_public_key) => cli_public_key.into_inner(),
+            None => DeleteAccessKeyAction::input_public_key().unwrap_or_else(|err| err.exit())
         };
         let next_action: Box<ActionSubcommand> = match item.next_action {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         DeleteAccessKeyAction {
             public_key,
@@ -45,19 +45,24 @@ impl From<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
 }
 
 impl DeleteAccessKeyAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec!["--public-key".to_string(), self.public_key.to_string()];
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
         // public_key_string: String,
-    ) {
-        println!("DeleteAccessKeyAction process: self:\n       {:?}", &self);
-        println!("DeleteAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        let public_key = near_crypto::PublicKey::from_str(&self.public_key).unwrap();
+    ) -> crate::common::CliResult {
+        log::debug!("DeleteAccessKeyAction process: self:\n       {:?}", &self);
+        log::debug!("DeleteAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         let action = near_primitives::transaction::Action::DeleteKey(
             near_primitives::transaction::DeleteKeyAction {
-                public_key
+                public_key: self.public_key.clone()
             }
         );
         let mut actions= prepopulated_unsigned_transaction.actions.clone();
@@ -67,22 +72,23 @@ impl DeleteAccessKeyAction {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_public_key) => args_add_public_key.process(unsigned_transaction, selected_server_url, "".to_string()).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_public_key) => args_add_public_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
 
     }
-    pub fn input_public_key() -> String {
-        Input::new()
+    pub fn input_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+        let public_key: crate::common::PublicKey = Input::new()
             .with_prompt("Enter the access key to remove it")
             .interact_text()
-            .unwrap()
+            .answer()?;
+        Ok(public_key.into_inner())
     }
 }
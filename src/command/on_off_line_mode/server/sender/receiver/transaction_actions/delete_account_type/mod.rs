@@ -2,6 +2,7 @@ use structopt::StructOpt;
 use dialoguer::{
     Input,
 };
+use crate::common::PromptAnswer;
 use async_recursion::async_recursion;
 
 use super::super::{
@@ -19,7 +20,7 @@ pub struct DeleteAccountAction {
 #[derive(Debug, StructOpt)]
 pub struct CliDeleteAccountAction {
     #[structopt(long)]
-    beneficiary_id: Option<String>,
+    beneficiary_id: Option<crate::common::ValidAccountId>,
     #[structopt(subcommand)]
     next_action: Option<CliActionSkipSubcommand>
 }
@@ -27,14 +28,14 @@ pub struct CliDeleteAccountAction {
 impl From<CliDeleteAccountAction> for DeleteAccountAction {
     fn from(item: CliDeleteAccountAction) -> Self {
         let beneficiary_id: String = match item.beneficiary_id {
-            Some(cli_account_id) => cli_account_id,
-            None => DeleteAccountAction::input_beneficiary_id()
+            Some(cli_account_id) => cli_account_id.into_inner(),
+            None => DeleteAccountAction::input_beneficiary_id().unwrap_or_else(|err| err.exit())
         };
         let next_action: Box<ActionSubcommand> = match item.next_action {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         DeleteAccountAction {
             beneficiary_id,
@@ -44,14 +45,20 @@ impl From<CliDeleteAccountAction> for DeleteAccountAction {
 }
 
 impl DeleteAccountAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec!["--beneficiary-id".to_string(), self.beneficiary_id.clone()];
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("DeleteAccountAction process: self:\n       {:?}", &self);
-        println!("DeleteAccountAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("DeleteAccountAction process: self:\n       {:?}", &self);
+        log::debug!("DeleteAccountAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
         let beneficiary_id: String = self.beneficiary_id.clone();
         let action = near_primitives::transaction::Action::DeleteAccount(
             near_primitives::transaction::DeleteAccountAction {
@@ -65,22 +72,23 @@ impl DeleteAccountAction {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, selected_server_url, "".to_string()).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
     }
-    pub fn input_beneficiary_id() -> String {
-        println!();
-        Input::new()
+    pub fn input_beneficiary_id() -> Result<String, crate::common::CliError> {
+        eprintln!();
+        let beneficiary_id: crate::common::ValidAccountId = Input::new()
             .with_prompt("Enter the beneficiary ID to delete this account ID")
             .interact_text()
-            .unwrap()
-        }
+            .answer()?;
+        Ok(beneficiary_id.into_inner())
+    }
 }
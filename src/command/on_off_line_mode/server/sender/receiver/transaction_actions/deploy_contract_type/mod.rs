@@ -0,0 +1,124 @@
+use structopt::StructOpt;
+use dialoguer::{
+    Input,
+};
+use crate::common::PromptAnswer;
+use async_recursion::async_recursion;
+
+use super::super::{
+    ActionSubcommand,
+    CliActionSkipSubcommand
+};
+
+
+const WASM_MAGIC_HEADER: &[u8] = b"\0asm";
+
+pub struct DeployContractAction {
+    pub file_path: std::path::PathBuf,
+    pub code: Vec<u8>,
+    pub next_action: Box<ActionSubcommand>
+}
+
+impl std::fmt::Debug for DeployContractAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeployContractAction")
+            .field("file_path", &self.file_path)
+            .field("code", &format!("<{} bytes>", self.code.len()))
+            .field("next_action", &self.next_action)
+            .finish()
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDeployContractAction {
+    #[structopt(parse(from_os_str))]
+    file_path: Option<std::path::PathBuf>,
+    #[structopt(subcommand)]
+    next_action: Option<CliActionSkipSubcommand>
+}
+
+impl From<CliDeployContractAction> for DeployContractAction {
+    fn from(item: CliDeployContractAction) -> Self {
+        let (file_path, code) = match item.file_path {
+            Some(cli_file_path) => {
+                let code = DeployContractAction::read_wasm_file(&cli_file_path)
+                    .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit());
+                (cli_file_path, code)
+            },
+            None => DeployContractAction::input_wasm_file().unwrap_or_else(|err| err.exit())
+        };
+        let next_action: Box<ActionSubcommand> = match item.next_action {
+            Some(cli_skip_action) => {
+                Box::new(ActionSubcommand::from(cli_skip_action))
+            },
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
+        };
+        DeployContractAction {
+            file_path,
+            code,
+            next_action
+        }
+    }
+}
+
+impl DeployContractAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.file_path.to_string_lossy().into_owned()];
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("DeployContractAction process: self:\n       {:?}", &self);
+        log::debug!("DeployContractAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        let action = near_primitives::transaction::Action::DeployContract(
+            near_primitives::transaction::DeployContractAction {
+                code: self.code.clone()
+            }
+        );
+        let mut actions= prepopulated_unsigned_transaction.actions.clone();
+        actions.push(action);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            .. prepopulated_unsigned_transaction
+        };
+        match *self.next_action {
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
+        }
+    }
+    pub fn input_wasm_file() -> Result<(std::path::PathBuf, Vec<u8>), crate::common::CliError> {
+        eprintln!();
+        loop {
+            let input: String = Input::new()
+                .with_prompt("Enter a path to the contract code (*.wasm file)")
+                .interact_text()
+                .answer()?;
+            let file_path = std::path::PathBuf::from(input);
+            match DeployContractAction::read_wasm_file(&file_path) {
+                Ok(code) => break Ok((file_path, code)),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    fn read_wasm_file(file_path: &std::path::Path) -> Result<Vec<u8>, String> {
+        let code = std::fs::read(file_path)
+            .map_err(|err| format!("Failed to read the contract code from {}: {}", file_path.display(), err))?;
+        if !code.starts_with(WASM_MAGIC_HEADER) {
+            return Err(format!("{} is not a WASM file (the magic header is missing)", file_path.display()));
+        };
+        Ok(code)
+    }
+}
@@ -4,3 +4,5 @@ pub mod delete_account_type;
 pub mod create_account_type;
 pub mod delete_access_key_type;
 pub mod transfer_near_tokens_type;
+pub mod stake_near_tokens_type;
+pub mod deploy_contract_type;
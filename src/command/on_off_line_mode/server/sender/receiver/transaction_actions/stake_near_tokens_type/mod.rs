@@ -0,0 +1,113 @@
+use structopt::StructOpt;
+use dialoguer::{
+    Input,
+};
+use crate::common::PromptAnswer;
+use async_recursion::async_recursion;
+
+use super::super::{
+    ActionSubcommand,
+    CliActionSkipSubcommand
+};
+use crate::common::NearBalance;
+
+
+#[derive(Debug)]
+pub struct StakeNEARTokensAction {
+    pub stake: NearBalance,
+    pub public_key: near_crypto::PublicKey,
+    pub next_action: Box<ActionSubcommand>
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliStakeNEARTokensAction {
+    stake: Option<NearBalance>,
+    #[structopt(long)]
+    public_key: Option<crate::common::PublicKey>,
+    #[structopt(subcommand)]
+    next_action: Option<CliActionSkipSubcommand>
+}
+
+impl From<CliStakeNEARTokensAction> for StakeNEARTokensAction {
+    fn from(item: CliStakeNEARTokensAction) -> Self {
+        let stake: NearBalance = match item.stake {
+            Some(cli_stake) => cli_stake,
+            None => StakeNEARTokensAction::input_stake().unwrap_or_else(|err| err.exit())
+        };
+        let public_key: near_crypto::PublicKey = match item.public_key {
+            Some(cli_public_key) => cli_public_key.into_inner(),
+            None => StakeNEARTokensAction::input_public_key().unwrap_or_else(|err| err.exit())
+        };
+        let next_action: Box<ActionSubcommand> = match item.next_action {
+            Some(cli_skip_action) => {
+                Box::new(ActionSubcommand::from(cli_skip_action))
+            },
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
+        };
+        StakeNEARTokensAction {
+            stake,
+            public_key,
+            next_action
+        }
+    }
+}
+
+impl StakeNEARTokensAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            self.stake.to_string(),
+            "--public-key".to_string(),
+            self.public_key.to_string(),
+        ];
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("StakeNEARTokensAction process: self:\n       {:?}", &self);
+        log::debug!("StakeNEARTokensAction process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        let stake = self.stake.0;
+        let action = near_primitives::transaction::Action::Stake(
+            near_primitives::transaction::StakeAction {
+                stake,
+                public_key: self.public_key.clone()
+            }
+        );
+        let mut actions= prepopulated_unsigned_transaction.actions.clone();
+        actions.push(action);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            .. prepopulated_unsigned_transaction
+        };
+        match *self.next_action {
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
+        }
+    }
+    pub fn input_stake() -> Result<NearBalance, crate::common::CliError> {
+        eprintln!();
+        Input::new()
+            .with_prompt("How many NEAR Tokens do you want to stake? (example: 10000 NEAR)")
+            .interact_text()
+            .answer()
+    }
+    pub fn input_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+        let public_key: crate::common::PublicKey = Input::new()
+            .with_prompt("Enter a public key of the validator")
+            .interact_text()
+            .answer()?;
+        Ok(public_key.into_inner())
+    }
+}
@@ -2,10 +2,11 @@ use structopt::StructOpt;
 use dialoguer::{
     Input,
 };
-use std::num::ParseIntError;
-use std::str::FromStr;
+use crate::common::PromptAnswer;
 use async_recursion::async_recursion;
 
+use crate::common::NearBalance;
+
 use super::super::{
     ActionSubcommand,
     CliActionSkipSubcommand
@@ -19,17 +20,21 @@ pub struct TransferNEARTokensAction {
 }
 
 impl TransferNEARTokensAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.amount.to_string()];
+        args.extend(self.next_action.to_cli_skip_args()?);
+        Some(args)
+    }
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        selected_server_url: String,
-    ) {
-        println!("TransferNEARTokens process: self:\n       {:?}", &self);
-        println!("TransferNEARTokens process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
-        let amount = match self.amount {
-            NearBalance(num) => num
-        };
+        connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::common::CliResult {
+        log::debug!("TransferNEARTokens process: self:\n       {:?}", &self);
+        log::debug!("TransferNEARTokens process: prepopulated_unsigned_transaction:\n       {:?}", &prepopulated_unsigned_transaction);
+        let amount = self.amount.0;
         let action = near_primitives::transaction::Action::Transfer(
             near_primitives::transaction::TransferAction {
                 deposit: amount,
@@ -42,17 +47,24 @@ impl TransferNEARTokensAction {
             .. prepopulated_unsigned_transaction
         };
         match *self.next_action {
-            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, selected_server_url).await,
-            // ActionSubcommand::CallFunction(args_function) => {},
-            // ActionSubcommand::StakeNEARTokens(args_stake) => {},
-            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, selected_server_url, "".to_string()).await,
-            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, selected_server_url).await,
-            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, selected_server_url).await,
-            _ => unreachable!("Error")
+            ActionSubcommand::TransferNEARTokens(args_transfer) => args_transfer.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CallFunction(args_function) => args_function.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::StakeNEARTokens(args_stake) => args_stake.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::CreateAccount(args_create_account) => args_create_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccount(args_delete_account) => args_delete_account.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::AddAccessKey(args_add_access_key) => args_add_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeleteAccessKey(args_delete_access_key) => args_delete_access_key.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::DeployContract(args_deploy_contract) => args_deploy_contract.process(unsigned_transaction, connection_config, output_format).await,
+            ActionSubcommand::Skip(args_skip) => args_skip.process(unsigned_transaction, connection_config, output_format).await,
         }
     }
+    pub fn input_amount() -> Result<NearBalance, crate::common::CliError> {
+        eprintln!();
+        Input::new()
+            .with_prompt("How many NEAR Tokens do you want to transfer? (example: 10 NEAR or 0.5 NEAR)")
+            .interact_text()
+            .answer()
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -62,50 +74,17 @@ pub struct CliTransferNEARTokensAction {
     next_action: Option<CliActionSkipSubcommand> 
 }
 
-impl NearBalance {
-    pub fn input_amount() -> Self {
-        let input: String = Input::new()
-            .with_prompt("How many NEAR Tokens do you want to transfer? (example: 10NEAR)")
-            .interact_text()
-            .unwrap();
-        NearBalance::from_str(&input).unwrap()
-    }
-}
-
-#[derive(Debug)]
-pub struct NearBalance (u128);
-
-impl FromStr for NearBalance {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let number: u128 = s.parse().unwrap_or_else(|ParseIntError| -> u128 {
-            let mut s: String = s.to_string().clone();
-            s.make_ascii_uppercase();
-            match s.contains("NEAR") {
-                true => {
-                    let num:u128 = s.trim_matches(char::is_alphabetic)
-                        .parse()
-                        .unwrap();
-                    num * 10u128.pow(24)
-                },
-                _ => 0
-            }
-        });
-        Ok(NearBalance(number))
-    }
-}
-
 impl From<CliTransferNEARTokensAction> for TransferNEARTokensAction {
     fn from(item: CliTransferNEARTokensAction) -> Self {
         let amount: NearBalance = match item.amount {
             Some(cli_amount) => cli_amount,
-            None => NearBalance::input_amount()
+            None => TransferNEARTokensAction::input_amount().unwrap_or_else(|err| err.exit())
         };
         let next_action: Box<ActionSubcommand> = match item.next_action {
             Some(cli_skip_action) => {
                 Box::new(ActionSubcommand::from(cli_skip_action))
             },
-            None => Box::new(ActionSubcommand::choose_action_command()) 
+            None => Box::new(ActionSubcommand::choose_action_command().unwrap_or_else(|err| err.exit()))
         };
         TransferNEARTokensAction {
             amount,
@@ -0,0 +1,92 @@
+use structopt::StructOpt;
+use strum_macros::{
+    EnumVariantNames,
+};
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
+
+use crate::common::{
+    ConnectionConfig,
+    NetworkId,
+};
+use crate::consts::{
+    TESTNET_API_SERVER_URL,
+    MAINNET_API_SERVER_URL,
+    BETANET_API_SERVER_URL,
+};
+
+
+/// Network selection for the commands which only need an RPC server (no
+/// sender/receiver pipeline behind it)
+#[derive(Debug, EnumVariantNames, StructOpt)]
+pub enum CliSelectNetwork {
+    Testnet,
+    Mainnet,
+    Betanet,
+    Custom(CliCustomNetwork),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCustomNetwork {
+    #[structopt(long)]
+    pub url: Option<String>,
+}
+
+impl From<CliSelectNetwork> for ConnectionConfig {
+    fn from(item: CliSelectNetwork) -> Self {
+        match item {
+            CliSelectNetwork::Testnet => ConnectionConfig {
+                network_id: NetworkId::Testnet,
+                url: TESTNET_API_SERVER_URL.to_string(),
+            },
+            CliSelectNetwork::Mainnet => ConnectionConfig {
+                network_id: NetworkId::Mainnet,
+                url: MAINNET_API_SERVER_URL.to_string(),
+            },
+            CliSelectNetwork::Betanet => ConnectionConfig {
+                network_id: NetworkId::Betanet,
+                url: BETANET_API_SERVER_URL.to_string(),
+            },
+            CliSelectNetwork::Custom(cli_custom_network) => ConnectionConfig {
+                network_id: NetworkId::Custom,
+                url: match cli_custom_network.url {
+                    Some(url) => url,
+                    None => input_custom_url().unwrap_or_else(|err| err.exit())
+                },
+            },
+        }
+    }
+}
+
+pub fn choose_connection_config() -> Result<ConnectionConfig, crate::common::CliError> {
+    eprintln!();
+    let networks = CliSelectNetwork::VARIANTS;
+    let select_network = crate::common::interact_select(
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select NEAR protocol RPC server:")
+            .items(&networks)
+            .default(0),
+    )?;
+    match select_network {
+        0 => Ok(ConnectionConfig::from(CliSelectNetwork::Testnet)),
+        1 => Ok(ConnectionConfig::from(CliSelectNetwork::Mainnet)),
+        2 => Ok(ConnectionConfig::from(CliSelectNetwork::Betanet)),
+        3 => Ok(ConnectionConfig {
+            network_id: NetworkId::Custom,
+            url: input_custom_url()?,
+        }),
+        _ => unreachable!("Error")
+    }
+}
+
+fn input_custom_url() -> Result<String, crate::common::CliError> {
+    Input::new()
+        .with_prompt("What is the RPC endpoint?")
+        .interact_text()
+        .answer()
+}
@@ -0,0 +1,184 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
+
+use super::select_network::{
+    CliSelectNetwork,
+    choose_connection_config,
+};
+
+
+/// Broadcast a transaction which has been signed somewhere else
+#[derive(Debug, StructOpt)]
+pub struct CliSendSignedTransaction {
+    /// The encoded signed transaction ("-" to read it from stdin)
+    signed_transaction: Option<String>,
+    #[structopt(long, parse(from_os_str), conflicts_with = "signed-transaction")]
+    file: Option<std::path::PathBuf>,
+    /// Broadcast the signed transaction from a bundle file
+    #[structopt(long, parse(from_os_str), conflicts_with_all = &["signed-transaction", "file"])]
+    bundle: Option<std::path::PathBuf>,
+    #[structopt(long, default_value = "base64", possible_values = crate::common::TransactionFormat::VARIANTS)]
+    encoding: crate::common::TransactionFormat,
+    /// Send without asking for a confirmation
+    #[structopt(long)]
+    yes: bool,
+    #[structopt(subcommand)]
+    selected_network: Option<CliSelectNetwork>,
+}
+
+#[derive(Debug)]
+pub struct SendSignedTransaction {
+    pub signed_transaction: near_primitives::transaction::SignedTransaction,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub yes: bool,
+}
+
+impl From<CliSendSignedTransaction> for SendSignedTransaction {
+    fn from(item: CliSendSignedTransaction) -> Self {
+        let encoded_transaction: Option<String> = match (item.signed_transaction, item.file) {
+            (Some(cli_signed_transaction), _) if cli_signed_transaction == "-" => {
+                Some(crate::common::read_encoded_transaction(std::path::Path::new("-"))
+                    .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit()))
+            },
+            (Some(cli_signed_transaction), _) => Some(cli_signed_transaction),
+            (None, Some(cli_file)) => Some(crate::common::read_encoded_transaction(&cli_file)
+                .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit())),
+            (None, None) => None
+        };
+        let bundle = item.bundle.as_ref().map(|cli_bundle| {
+            crate::bundle::TransactionBundle::read(cli_bundle).unwrap_or_else(|err| err.exit())
+        });
+        let signed_transaction = match (&bundle, encoded_transaction) {
+            (Some(bundle), _) => bundle.signed_transaction().unwrap_or_else(|err| err.exit()),
+            (None, Some(encoded_transaction)) => {
+                SendSignedTransaction::decode_signed_transaction(&encoded_transaction, item.encoding)
+                    .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit())
+            },
+            (None, None) => SendSignedTransaction::input_signed_transaction().unwrap_or_else(|err| err.exit())
+        };
+        let connection_config = match item.selected_network {
+            Some(cli_selected_network) => crate::common::ConnectionConfig::from(cli_selected_network),
+            None => choose_connection_config().unwrap_or_else(|err| err.exit())
+        };
+        if let Some(bundle_network_id) = bundle.and_then(|bundle| bundle.network_id) {
+            if bundle_network_id != connection_config.network_id {
+                crate::common::CliError::ParseError(format!(
+                    "The bundle was prepared for {}, but the transaction is going to be sent to {}",
+                    bundle_network_id,
+                    connection_config.network_id
+                ))
+                .exit()
+            };
+        };
+        SendSignedTransaction {
+            signed_transaction,
+            connection_config,
+            yes: item.yes,
+        }
+    }
+}
+
+impl SendSignedTransaction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format = crate::common::TransactionFormat::Base64;
+        let format_str: &str = format.into();
+        let mut args = vec![
+            "--encoding".to_string(),
+            format_str.to_string(),
+            format.encode(
+                &self.signed_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ),
+        ];
+        if self.yes {
+            args.push("--yes".to_string());
+        };
+        args.extend(self.connection_config.to_cli_args());
+        args
+    }
+    pub fn choose_send_signed_transaction() -> Result<Self, crate::common::CliError> {
+        let signed_transaction = SendSignedTransaction::input_signed_transaction()?;
+        let connection_config = choose_connection_config()?;
+        Ok(SendSignedTransaction {
+            signed_transaction,
+            connection_config,
+            yes: false,
+        })
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        log::debug!("---  Signed transaction:   ---    {:#?}", &self.signed_transaction);
+        eprintln!();
+        crate::common::print_transaction_summary(
+            &self.signed_transaction.transaction,
+            Some(self.connection_config.network_id),
+        );
+        eprintln!();
+        if !self.yes && !crate::common::confirm_broadcasting()? {
+            return Err(crate::common::CliError::UserCancelled);
+        };
+        log::info!("Broadcasting the transaction to {}", self.connection_config.url);
+        let serialize_to_base64 = near_primitives::serialize::to_base64(
+            self.signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        );
+        let transaction_info = self.connection_config
+            .rpc_client()
+            .broadcast_tx_commit(serialize_to_base64.clone())
+            .await
+            .map_err(|err| {
+                crate::common::CliError::RpcError(format!("Failed to broadcast the transaction: {:?}", err))
+            })?;
+        let transaction_output = crate::common::TransactionOutput {
+            transaction: &self.signed_transaction.transaction,
+            network_id: Some(self.connection_config.network_id),
+            encoding: crate::common::TransactionFormat::Base64,
+            unsigned_transaction: None,
+            signed_transaction: Some(serialize_to_base64),
+            outcome: Some(transaction_info),
+        };
+        transaction_output.print(output_format);
+        transaction_output.status()
+    }
+    pub fn input_signed_transaction() -> Result<near_primitives::transaction::SignedTransaction, crate::common::CliError> {
+        eprintln!();
+        let formats = crate::common::TransactionFormat::VARIANTS;
+        let select_format = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the encoding of the signed transaction")
+                .items(&formats)
+                .default(0),
+        )?;
+        let format = match select_format {
+            0 => crate::common::TransactionFormat::Base64,
+            1 => crate::common::TransactionFormat::Hex,
+            _ => unreachable!("Error")
+        };
+        loop {
+            let input: String = Input::new()
+                .with_prompt("Enter the signed transaction")
+                .interact_text()
+                .answer()?;
+            match SendSignedTransaction::decode_signed_transaction(&input, format) {
+                Ok(signed_transaction) => break Ok(signed_transaction),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    fn decode_signed_transaction(
+        encoded_transaction: &str,
+        format: crate::common::TransactionFormat,
+    ) -> Result<near_primitives::transaction::SignedTransaction, String> {
+        let serialized_transaction = format.decode(encoded_transaction)?;
+        near_primitives::transaction::SignedTransaction::try_from_slice(&serialized_transaction)
+            .map_err(|err| format!("The input is not a signed transaction: {}", err))
+    }
+}
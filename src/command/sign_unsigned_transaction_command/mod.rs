@@ -0,0 +1,254 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
+
+
+/// Sign a transaction which has been constructed somewhere else (no network
+/// access is required)
+#[derive(Debug, StructOpt)]
+pub struct CliSignUnsignedTransaction {
+    /// The encoded unsigned transaction ("-" to read it from stdin)
+    unsigned_transaction: Option<String>,
+    #[structopt(long, parse(from_os_str), conflicts_with = "unsigned-transaction")]
+    file: Option<std::path::PathBuf>,
+    /// Sign the transaction in a bundle file and add the signature to it
+    #[structopt(long, parse(from_os_str), conflicts_with_all = &["unsigned-transaction", "file"])]
+    bundle: Option<std::path::PathBuf>,
+    #[structopt(long, default_value = "base64", possible_values = crate::common::TransactionFormat::VARIANTS)]
+    encoding: crate::common::TransactionFormat,
+    #[structopt(long, conflicts_with = "seed-phrase")]
+    signer_secret_key: Option<crate::common::SecretKey>,
+    #[structopt(long)]
+    seed_phrase: Option<crate::common::SeedPhrase>,
+    #[structopt(long, default_value = "m/44'/397'/0'")]
+    seed_phrase_hd_path: slip10::BIP32Path,
+    /// Sign without asking for a confirmation
+    #[structopt(long)]
+    yes: bool,
+}
+
+pub struct SignUnsignedTransaction {
+    pub unsigned_transaction: near_primitives::transaction::Transaction,
+    pub format: crate::common::TransactionFormat,
+    pub signer_secret_key: crate::common::SecretKey,
+    /// Set when the key has been derived from a seed phrase
+    pub seed_phrase_hd_path: Option<slip10::BIP32Path>,
+    pub bundle: Option<std::path::PathBuf>,
+    pub yes: bool,
+}
+
+impl std::fmt::Debug for SignUnsignedTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignUnsignedTransaction")
+            .field("unsigned_transaction", &self.unsigned_transaction)
+            .field("format", &self.format)
+            .field("signer_secret_key", &crate::common::REDACTED_SECRET)
+            .field(
+                "seed_phrase_hd_path",
+                &self.seed_phrase_hd_path.as_ref().map(crate::common::bip32path_to_string),
+            )
+            .field("bundle", &self.bundle)
+            .field("yes", &self.yes)
+            .finish()
+    }
+}
+
+impl From<CliSignUnsignedTransaction> for SignUnsignedTransaction {
+    fn from(item: CliSignUnsignedTransaction) -> Self {
+        let encoded_transaction: Option<String> = match (item.unsigned_transaction, item.file) {
+            (Some(cli_unsigned_transaction), _) if cli_unsigned_transaction == "-" => {
+                Some(crate::common::read_encoded_transaction(std::path::Path::new("-"))
+                    .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit()))
+            },
+            (Some(cli_unsigned_transaction), _) => Some(cli_unsigned_transaction),
+            (None, Some(cli_file)) => Some(crate::common::read_encoded_transaction(&cli_file)
+                .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit())),
+            (None, None) => None
+        };
+        let bundle_transaction = item.bundle.as_ref().map(|cli_bundle| {
+            crate::bundle::TransactionBundle::read(cli_bundle)
+                .and_then(|bundle| bundle.transaction())
+                .unwrap_or_else(|err| err.exit())
+        });
+        let (unsigned_transaction, format) = match (bundle_transaction, encoded_transaction) {
+            (Some(unsigned_transaction), _) => (unsigned_transaction, item.encoding),
+            (None, Some(encoded_transaction)) => {
+                let unsigned_transaction = SignUnsignedTransaction::decode_unsigned_transaction(&encoded_transaction, item.encoding)
+                    .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit());
+                (unsigned_transaction, item.encoding)
+            },
+            (None, None) => SignUnsignedTransaction::input_unsigned_transaction().unwrap_or_else(|err| err.exit())
+        };
+        let (signer_secret_key, seed_phrase_hd_path) = match (item.signer_secret_key, item.seed_phrase) {
+            (Some(cli_signer_secret_key), _) => (cli_signer_secret_key, None),
+            (None, Some(cli_seed_phrase)) => {
+                let signer_secret_key = crate::common::get_secret_key_from_seed_phrase(
+                    cli_seed_phrase.as_str(),
+                    &item.seed_phrase_hd_path,
+                )
+                .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit());
+                (signer_secret_key, Some(item.seed_phrase_hd_path))
+            },
+            (None, None) => SignUnsignedTransaction::input_signer_secret_key().unwrap_or_else(|err| err.exit())
+        };
+        SignUnsignedTransaction {
+            unsigned_transaction,
+            format,
+            signer_secret_key,
+            seed_phrase_hd_path,
+            bundle: item.bundle,
+            yes: item.yes,
+        }
+    }
+}
+
+impl SignUnsignedTransaction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format: &str = self.format.into();
+        let mut args = match &self.bundle {
+            Some(bundle) => vec!["--bundle".to_string(), bundle.to_string_lossy().into_owned()],
+            None => vec![
+                self.format.encode(
+                    &self.unsigned_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            ],
+        };
+        args.extend(vec!["--encoding".to_string(), format.to_string()]);
+        match &self.seed_phrase_hd_path {
+            Some(seed_phrase_hd_path) => args.extend(vec![
+                "--seed-phrase".to_string(),
+                crate::common::REDACTED_SECRET.to_string(),
+                "--seed-phrase-hd-path".to_string(),
+                crate::common::bip32path_to_string(seed_phrase_hd_path),
+            ]),
+            None => args.extend(vec![
+                "--signer-secret-key".to_string(),
+                crate::common::REDACTED_SECRET.to_string(),
+            ]),
+        };
+        if self.yes {
+            args.push("--yes".to_string());
+        };
+        args
+    }
+    pub fn choose_sign_unsigned_transaction() -> Result<Self, crate::common::CliError> {
+        let (unsigned_transaction, format) = SignUnsignedTransaction::input_unsigned_transaction()?;
+        let (signer_secret_key, seed_phrase_hd_path) = SignUnsignedTransaction::input_signer_secret_key()?;
+        Ok(SignUnsignedTransaction {
+            unsigned_transaction,
+            format,
+            signer_secret_key,
+            seed_phrase_hd_path,
+            bundle: None,
+            yes: false,
+        })
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        eprintln!();
+        crate::common::print_transaction_summary(&self.unsigned_transaction, None);
+        eprintln!();
+        if self.signer_secret_key.public_key() != self.unsigned_transaction.public_key {
+            return Err(crate::common::CliError::SigningError(format!(
+                "The transaction expects to be signed by {}, but the provided key is {}",
+                self.unsigned_transaction.public_key,
+                self.signer_secret_key.public_key()
+            )));
+        };
+        if !self.yes && !crate::common::confirm_signing()? {
+            return Err(crate::common::CliError::UserCancelled);
+        };
+        let signature = self.signer_secret_key
+            .sign(self.unsigned_transaction.get_hash().as_ref());
+        if let Some(ref bundle_path) = self.bundle {
+            let mut bundle = crate::bundle::TransactionBundle::read(bundle_path)?;
+            bundle.set_signature(&signature)?;
+            bundle.write(bundle_path)?;
+            eprintln!("The signature has been added to the bundle {}", bundle_path.display());
+        };
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, self.unsigned_transaction);
+        let encoded_transaction = self.format.encode(
+            &signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        );
+        crate::common::TransactionOutput {
+            transaction: &signed_transaction.transaction,
+            network_id: None,
+            encoding: self.format,
+            unsigned_transaction: None,
+            signed_transaction: Some(encoded_transaction),
+            outcome: None,
+        }
+        .print(output_format);
+        Ok(())
+    }
+    pub fn input_unsigned_transaction() -> Result<(near_primitives::transaction::Transaction, crate::common::TransactionFormat), crate::common::CliError> {
+        eprintln!();
+        let formats = crate::common::TransactionFormat::VARIANTS;
+        let select_format = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the encoding of the unsigned transaction")
+                .items(&formats)
+                .default(0),
+        )?;
+        let format = match select_format {
+            0 => crate::common::TransactionFormat::Base64,
+            1 => crate::common::TransactionFormat::Hex,
+            _ => unreachable!("Error")
+        };
+        loop {
+            let input: String = Input::new()
+                .with_prompt("Enter the unsigned transaction")
+                .interact_text()
+                .answer()?;
+            match SignUnsignedTransaction::decode_unsigned_transaction(&input, format) {
+                Ok(unsigned_transaction) => break Ok((unsigned_transaction, format)),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    /// Returns the HD path as well when the key is derived from a seed phrase
+    pub fn input_signer_secret_key(
+    ) -> Result<(crate::common::SecretKey, Option<slip10::BIP32Path>), crate::common::CliError> {
+        eprintln!();
+        let choose_key = vec![
+            "Secret key (ed25519:...)",
+            "Seed phrase",
+        ];
+        let select_key = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("How do you want to provide the signing key?")
+                .items(&choose_key)
+                .default(0),
+        )?;
+        match select_key {
+            0 => Ok((crate::common::input_secret("Enter sender's private key")?, None)),
+            1 => {
+                let seed_phrase: crate::common::SeedPhrase = crate::common::input_secret("Enter the seed phrase")?;
+                let seed_phrase_hd_path = crate::utils_subcommand::generate_keypair_subcommand::GenerateKeypair::input_seed_phrase_hd_path()?;
+                let signer_secret_key =
+                    crate::common::get_secret_key_from_seed_phrase(seed_phrase.as_str(), &seed_phrase_hd_path)
+                        .map_err(crate::common::CliError::ParseError)?;
+                Ok((signer_secret_key, Some(seed_phrase_hd_path)))
+            },
+            _ => unreachable!("Error")
+        }
+    }
+    fn decode_unsigned_transaction(
+        encoded_transaction: &str,
+        format: crate::common::TransactionFormat,
+    ) -> Result<near_primitives::transaction::Transaction, String> {
+        let serialized_transaction = format.decode(encoded_transaction)?;
+        near_primitives::transaction::Transaction::try_from_slice(&serialized_transaction)
+            .map_err(|err| format!("The input is not an unsigned transaction: {}", err))
+    }
+}
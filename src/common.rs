@@ -1,8 +1,52 @@
 use std::convert::TryInto;
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+
+#[derive(Debug, derive_more::Display)]
+pub enum CliError {
+    #[display(fmt = "Invalid input: {}", _0)]
+    ParseError(String),
+    #[display(fmt = "RPC request failed: {}", _0)]
+    RpcError(String),
+    #[display(fmt = "Signing failed: {}", _0)]
+    SigningError(String),
+    #[display(fmt = "I/O failed: {}", _0)]
+    IoError(String),
+    #[display(fmt = "Cancelled by the user")]
+    UserCancelled,
+}
+
+impl CliError {
+    /// Every kind of failure gets its own exit code, so scripts can tell a
+    /// typo from a network failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::ParseError(_) => 2,
+            CliError::RpcError(_) => 3,
+            CliError::SigningError(_) => 4,
+            CliError::IoError(_) => 5,
+            CliError::UserCancelled => 130,
+        }
+    }
+
+    /// Reports the error and terminates the process; used while the command
+    /// line arguments are being converted, before there is anything to
+    /// propagate the error to
+    pub fn exit(self) -> ! {
+        eprintln!("Error: {}", self);
+        std::process::exit(self.exit_code())
+    }
+}
+
+pub type CliResult = Result<(), CliError>;
 
 
 #[derive(
     Debug,
+    Clone,
+    Copy,
+    PartialEq,
     strum_macros::IntoStaticStr,
     strum_macros::EnumString,
     strum_macros::EnumVariantNames,
@@ -13,10 +57,29 @@ pub enum OutputFormat {
     #[default]
     Plaintext,
     Json,
+    /// Transaction payloads are shown as terminal QR codes (several of them
+    /// when the payload is too large for one)
+    Qr,
+}
+
+impl OutputFormat {
+    /// QR codes are meant for carrying transactions between machines, so the
+    /// commands which print anything else refuse them
+    pub fn reject_qr(self, command: &str) -> CliResult {
+        match self {
+            OutputFormat::Qr => Err(CliError::ParseError(format!(
+                "QR codes are only available for transactions, {} supports plaintext and json",
+                command
+            ))),
+            OutputFormat::Plaintext | OutputFormat::Json => Ok(()),
+        }
+    }
 }
 
 #[derive(
     Debug,
+    Clone,
+    Copy,
     strum_macros::IntoStaticStr,
     strum_macros::EnumString,
     strum_macros::EnumVariantNames,
@@ -29,6 +92,184 @@ pub enum TransactionFormat {
     Hex,
 }
 
+impl TransactionFormat {
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            TransactionFormat::Base64 => near_primitives::serialize::to_base64(bytes),
+            TransactionFormat::Hex => hex::encode(bytes),
+        }
+    }
+
+    /// Multi-part QR code payloads are accepted as well, as long as all the
+    /// parts are there (in any order, separated with whitespace)
+    pub fn decode(&self, encoded: &str) -> Result<Vec<u8>, String> {
+        let encoded = join_qr_parts(encoded)?;
+        match self {
+            TransactionFormat::Base64 => near_primitives::serialize::from_base64(&encoded)
+                .map_err(|err| format!("The transaction is not a valid base64: {}", err)),
+            TransactionFormat::Hex => hex::decode(&encoded)
+                .map_err(|err| format!("The transaction is not a valid hex: {}", err)),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum SignatureFormat {
+    /// "ed25519:<base58>", the way NEAR tools print signatures
+    #[default]
+    Base58,
+    /// The bare signature bytes, without the key type
+    Base64,
+}
+
+impl SignatureFormat {
+    pub fn encode(&self, signature: &near_crypto::Signature) -> String {
+        match self {
+            SignatureFormat::Base58 => signature.to_string(),
+            SignatureFormat::Base64 => {
+                let signature = signature.to_string();
+                let signature_data = signature.splitn(2, ':').nth(1).unwrap_or_default();
+                near_primitives::serialize::to_base64(
+                    bs58::decode(signature_data)
+                        .into_vec()
+                        .expect("Signature is expected to be printed in base58"),
+                )
+            }
+        }
+    }
+
+    /// The key type comes from the public key, since a base64 signature does
+    /// not carry it (and a base58 one may omit it)
+    pub fn decode(&self, encoded: &str, key_type: near_crypto::KeyType) -> Result<near_crypto::Signature, String> {
+        let encoded = encoded.trim();
+        let signature = match self {
+            SignatureFormat::Base58 if encoded.contains(':') => encoded.to_string(),
+            SignatureFormat::Base58 => format!("{}:{}", key_type, encoded),
+            SignatureFormat::Base64 => {
+                let signature_data = near_primitives::serialize::from_base64(encoded)
+                    .map_err(|err| format!("The signature is not a valid base64: {}", err))?;
+                format!("{}:{}", key_type, bs58::encode(signature_data).into_string())
+            }
+        };
+        near_crypto::Signature::from_str(&signature)
+            .map_err(|err| format!("Invalid signature: {:?}", err))
+    }
+}
+
+pub fn input_signature_format() -> Result<SignatureFormat, CliError> {
+    eprintln!();
+    let formats = <SignatureFormat as strum::VariantNames>::VARIANTS;
+    let select_format = interact_select(
+        dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("Select the encoding of the signature")
+            .items(&formats)
+            .default(0),
+    )?;
+    match select_format {
+        0 => Ok(SignatureFormat::Base58),
+        1 => Ok(SignatureFormat::Base64),
+        _ => unreachable!("Error")
+    }
+}
+
+/// Marks a part of a payload which does not fit into a single QR code:
+/// "near-tx:<part>/<total>:<chunk>"
+const QR_PART_PREFIX: &str = "near-tx:";
+/// Keeps every QR code small enough for a terminal and a phone camera
+const QR_PART_LENGTH: usize = 300;
+
+pub fn split_into_qr_parts(payload: &str) -> Vec<String> {
+    if payload.len() <= QR_PART_LENGTH {
+        return vec![payload.to_string()];
+    };
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(QR_PART_LENGTH).collect();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            format!(
+                "{}{}/{}:{}",
+                QR_PART_PREFIX,
+                index + 1,
+                chunks.len(),
+                String::from_utf8_lossy(chunk)
+            )
+        })
+        .collect()
+}
+
+pub fn join_qr_parts(input: &str) -> Result<String, String> {
+    if !input.trim_start().starts_with(QR_PART_PREFIX) {
+        return Ok(input.trim().to_string());
+    };
+    let mut parts = input
+        .split_whitespace()
+        .map(|part| {
+            let invalid_part = || format!("\"{}\" is not a part of a QR code payload", part);
+            let part = part.strip_prefix(QR_PART_PREFIX).ok_or_else(invalid_part)?;
+            let separator_index = part.find(':').ok_or_else(invalid_part)?;
+            let (position, chunk) = (&part[..separator_index], &part[separator_index + 1..]);
+            let slash_index = position.find('/').ok_or_else(invalid_part)?;
+            let index: usize = position[..slash_index].parse().map_err(|_| invalid_part())?;
+            let total: usize = position[slash_index + 1..].parse().map_err(|_| invalid_part())?;
+            Ok((index, total, chunk))
+        })
+        .collect::<Result<Vec<(usize, usize, &str)>, String>>()?;
+    parts.sort_by_key(|(index, _, _)| *index);
+    let total = parts[0].1;
+    let is_complete = parts.len() == total
+        && parts
+            .iter()
+            .enumerate()
+            .all(|(position, (index, part_total, _))| *index == position + 1 && *part_total == total);
+    if !is_complete {
+        return Err(format!(
+            "The QR code payload is incomplete: expected parts 1 to {}, got {}",
+            total,
+            parts
+                .iter()
+                .map(|(index, part_total, _)| format!("{}/{}", index, part_total))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    };
+    Ok(parts.into_iter().map(|(_, _, chunk)| chunk).collect())
+}
+
+/// Renders the payload as one or more QR codes; on a terminal every next part
+/// is shown after Enter is pressed
+pub fn print_qr_codes(payload: &str) {
+    let parts = split_into_qr_parts(payload);
+    let is_interactive = dialoguer::console::Term::stdout().is_term();
+    for (index, part) in parts.iter().enumerate() {
+        let qr_code = qrcode::QrCode::with_error_correction_level(part, qrcode::EcLevel::L)
+            .expect("The QR code part is not expected to exceed the QR code capacity");
+        if parts.len() > 1 {
+            println!("Part {}/{}:", index + 1, parts.len());
+        };
+        println!(
+            "{}",
+            qr_code
+                .render::<qrcode::render::unicode::Dense1x2>()
+                .quiet_zone(true)
+                .build()
+        );
+        if is_interactive && index + 1 < parts.len() {
+            eprintln!("Press Enter to show the next part");
+            let _ = dialoguer::console::Term::stderr().read_line();
+        };
+    }
+}
+
 #[derive(derive_more::AsRef)]
 pub struct BlobAsBase58String<T>
 where
@@ -50,15 +291,15 @@ impl<T> std::str::FromStr for BlobAsBase58String<T>
 where
     for<'a> T: std::convert::TryFrom<&'a [u8]> + AsRef<[u8]>,
 {
-    type Err = &'static str;
+    type Err = String;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         Ok(Self {
             inner: near_primitives::serialize::from_base(value)
-                .map_err(|_| "err")?
+                .map_err(|err| format!("{} is not a valid base58 string: {}", value, err))?
                 .as_slice()
                 .try_into()
-                .map_err(|_| "err")?,
+                .map_err(|_| format!("{} has an unexpected length", value))?,
         })
     }
 }
@@ -71,3 +312,858 @@ where
         self.inner
     }
 }
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    strum_macros::IntoStaticStr,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkId {
+    Testnet,
+    Mainnet,
+    Betanet,
+    Custom,
+}
+
+impl NetworkId {
+    /// Custom networks have no explorer which could be linked to
+    pub fn explorer_url(&self) -> Option<&'static str> {
+        match self {
+            NetworkId::Testnet => Some("https://explorer.testnet.near.org"),
+            NetworkId::Mainnet => Some("https://explorer.near.org"),
+            NetworkId::Betanet => Some("https://explorer.betanet.near.org"),
+            NetworkId::Custom => None,
+        }
+    }
+}
+
+/// The RPC server together with the network it belongs to, so every stage of
+/// the pipeline knows where the transaction is going to end up
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    pub network_id: NetworkId,
+    pub url: String,
+}
+
+impl ConnectionConfig {
+    pub fn rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
+        near_jsonrpc_client::new_client(&self.url)
+    }
+
+    /// The network subcommand which leads to this connection config
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self.network_id {
+            NetworkId::Custom => vec!["custom".to_string(), "--url".to_string(), self.url.clone()],
+            network_id => vec![network_id.to_string()],
+        }
+    }
+}
+
+/// Reads an encoded transaction from the file, or from stdin when the path is "-"
+pub fn read_encoded_transaction(path: &std::path::Path) -> Result<String, String> {
+    if path == std::path::Path::new("-") {
+        let mut encoded_transaction = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut encoded_transaction)
+            .map_err(|err| format!("Failed to read the transaction from stdin: {}", err))?;
+        Ok(encoded_transaction.trim().to_string())
+    } else {
+        std::fs::read_to_string(path)
+            .map(|encoded_transaction| encoded_transaction.trim().to_string())
+            .map_err(|err| format!("Failed to read the transaction from {}: {}", path.display(), err))
+    }
+}
+
+/// Reads a message to sign or verify byte for byte (nothing is trimmed), from
+/// the file or from stdin when the path is "-"
+pub fn read_message(path: &std::path::Path) -> Result<Vec<u8>, String> {
+    if path == std::path::Path::new("-") {
+        let mut message = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut message)
+            .map_err(|err| format!("Failed to read the message from stdin: {}", err))?;
+        Ok(message)
+    } else {
+        std::fs::read(path)
+            .map_err(|err| format!("Failed to read the message from {}: {}", path.display(), err))
+    }
+}
+
+pub fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
+    const HARDEND: u32 = 1 << 31;
+
+    format!(
+        "m/{}",
+        (0..bip32path.depth())
+            .map(|index| {
+                let value = *bip32path.index(index).unwrap();
+                if value < HARDEND {
+                    value.to_string()
+                } else {
+                    format!("{}'", value - HARDEND)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    )
+}
+
+pub fn get_secret_key_from_seed_phrase(
+    seed_phrase: &str,
+    seed_phrase_hd_path: &slip10::BIP32Path,
+) -> Result<SecretKey, String> {
+    let master_seed = zeroize::Zeroizing::new(
+        bip39::Mnemonic::parse(seed_phrase)
+            .map_err(|err| format!("Invalid seed phrase: {}", err))?
+            .to_seed(""),
+    );
+    let mut derived_private_key = slip10::derive_key_from_path(
+        master_seed.as_ref(),
+        slip10::Curve::Ed25519,
+        seed_phrase_hd_path,
+    )
+    .map_err(|err| format!("Key derivation from path failed: {:?}", err))?;
+    let secret = ed25519_dalek::SecretKey::from_bytes(&derived_private_key.key);
+    derived_private_key.key.zeroize();
+    let secret_keypair = {
+        let secret = secret.map_err(|err| format!("Invalid derived secret key: {}", err))?;
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        ed25519_dalek::Keypair { secret, public }
+    };
+    let secret_keypair_bytes = zeroize::Zeroizing::new(secret_keypair.to_bytes());
+    let secret_keypair_str = zeroize::Zeroizing::new(format!(
+        "ed25519:{}",
+        bs58::encode(secret_keypair_bytes.as_ref()).into_string()
+    ));
+    near_crypto::SecretKey::from_str(&secret_keypair_str)
+        .map(SecretKey)
+        .map_err(|err| format!("Invalid derived secret key: {:?}", err))
+}
+
+/// An account ID which follows the NEAR naming rules: 2 to 64 characters of
+/// lowercase letters, digits and separators ("-", "_", "."), where separators
+/// never start, end or follow each other. Implicit accounts (64 hex
+/// characters) satisfy the same rules.
+#[derive(Debug, Clone, PartialEq, derive_more::Display)]
+pub struct ValidAccountId(near_primitives::types::AccountId);
+
+impl ValidAccountId {
+    pub fn into_inner(self) -> near_primitives::types::AccountId {
+        self.0
+    }
+}
+
+impl std::str::FromStr for ValidAccountId {
+    type Err = String;
+
+    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        if account_id.len() < 2 || account_id.len() > 64 {
+            return Err(format!(
+                "\"{}\" is not a valid account ID: it must be from 2 to 64 characters long",
+                account_id
+            ));
+        };
+        let is_separator = |c: char| c == '-' || c == '_' || c == '.';
+        if let Some(invalid_char) = account_id
+            .chars()
+            .find(|&c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || is_separator(c)))
+        {
+            return Err(format!(
+                "\"{}\" is not a valid account ID: '{}' is not allowed (use lowercase letters, digits, '-', '_' and '.')",
+                account_id, invalid_char
+            ));
+        };
+        let starts_or_ends_with_separator = account_id.starts_with(is_separator)
+            || account_id.ends_with(is_separator);
+        let has_adjacent_separators = account_id
+            .chars()
+            .zip(account_id.chars().skip(1))
+            .any(|(a, b)| is_separator(a) && is_separator(b));
+        if starts_or_ends_with_separator || has_adjacent_separators {
+            return Err(format!(
+                "\"{}\" is not a valid account ID: separators cannot start or end it, or follow each other",
+                account_id
+            ));
+        };
+        Ok(ValidAccountId(account_id.to_string()))
+    }
+}
+
+/// Checks the "<key type>:<base58 data>" layout of a key, so a typo gets a
+/// clearer explanation than the one near_crypto gives
+fn check_key_format(
+    key: &str,
+    key_kind: &str,
+    ed25519_length: usize,
+    secp256k1_length: usize,
+) -> Result<(), String> {
+    let (key_type, key_data) = match key.find(':') {
+        Some(separator_index) => (&key[..separator_index], &key[separator_index + 1..]),
+        None => ("ed25519", key),
+    };
+    let expected_length = match key_type {
+        "ed25519" => ed25519_length,
+        "secp256k1" => secp256k1_length,
+        _ => {
+            return Err(format!(
+                "The {} has an unknown prefix \"{}:\" (expected \"ed25519:\" or \"secp256k1:\")",
+                key_kind, key_type
+            ))
+        }
+    };
+    let key_bytes = zeroize::Zeroizing::new(
+        bs58::decode(key_data)
+            .into_vec()
+            .map_err(|err| format!("The {} is not a valid base58 string: {}", key_kind, err))?,
+    );
+    if key_bytes.len() != expected_length {
+        return Err(format!(
+            "The {} must be {} bytes long for {}, but it is {} bytes long",
+            key_kind,
+            expected_length,
+            key_type,
+            key_bytes.len()
+        ));
+    };
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, derive_more::Display)]
+pub struct PublicKey(near_crypto::PublicKey);
+
+impl PublicKey {
+    pub fn into_inner(self) -> near_crypto::PublicKey {
+        self.0
+    }
+}
+
+impl std::str::FromStr for PublicKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        check_key_format(value.trim(), "public key", 32, 64)?;
+        near_crypto::PublicKey::from_str(value.trim())
+            .map(PublicKey)
+            .map_err(|err| format!("Invalid public key: {:?}", err))
+    }
+}
+
+/// A secret key which is wiped from memory when it is dropped. Only ed25519
+/// keys can be wiped: near_crypto keeps secp256k1 keys inside the secp256k1
+/// crate, which gives no way to overwrite them. Copies which the compiler makes
+/// when the key is moved are not wiped either.
+#[derive(Clone, PartialEq, derive_more::Display)]
+pub struct SecretKey(near_crypto::SecretKey);
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SecretKey").field(&REDACTED_SECRET).finish()
+    }
+}
+
+impl std::ops::Deref for SecretKey {
+    type Target = near_crypto::SecretKey;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<near_crypto::SecretKey> for SecretKey {
+    fn from(secret_key: near_crypto::SecretKey) -> Self {
+        SecretKey(secret_key)
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        if let near_crypto::SecretKey::ED25519(ref mut secret_key) = self.0 {
+            secret_key.0.zeroize();
+        };
+    }
+}
+
+impl std::str::FromStr for SecretKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        check_key_format(value.trim(), "secret key", 64, 32)?;
+        near_crypto::SecretKey::from_str(value.trim())
+            .map(SecretKey)
+            .map_err(|err| format!("Invalid secret key: {:?}", err))
+    }
+}
+
+/// An amount of NEAR tokens, kept in yoctoNEAR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearBalance(pub near_primitives::types::Balance);
+
+impl std::fmt::Display for NearBalance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_near_amount(self.0))
+    }
+}
+
+/// Accepts amounts like "10 NEAR", "0.25NEAR", "500 milliNEAR" or
+/// "1000 yoctoNEAR"; a unit is required for anything but zero
+impl std::str::FromStr for NearBalance {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (number, unit) = value.split_at(
+            value.find(|c: char| c.is_ascii_alphabetic()).unwrap_or_else(|| value.len()),
+        );
+        let number = number.trim();
+        let decimals: usize = match unit.to_ascii_lowercase().as_str() {
+            "near" => 24,
+            "millinear" => 21,
+            "yoctonear" => 0,
+            "" if !number.is_empty() && number.trim_start_matches('0').is_empty() => 0,
+            "" => {
+                return Err(format!(
+                    "\"{}\" has no unit, use NEAR, milliNEAR or yoctoNEAR (example: 10 NEAR)",
+                    value
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "\"{}\" is not a known unit, use NEAR, milliNEAR or yoctoNEAR",
+                    unit
+                ))
+            }
+        };
+        let (integer, fraction) = match number.find('.') {
+            Some(dot_index) => (&number[..dot_index], &number[dot_index + 1..]),
+            None => (number, ""),
+        };
+        if integer.is_empty() && fraction.is_empty()
+            || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(format!("\"{}\" is not a valid amount", value));
+        };
+        if fraction.len() > decimals {
+            return Err(format!(
+                "\"{}\" has more decimal places than {} allows",
+                value, unit
+            ));
+        };
+        let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+        let digits = digits.trim_start_matches('0');
+        if digits.is_empty() {
+            return Ok(NearBalance(0));
+        };
+        digits
+            .parse::<near_primitives::types::Balance>()
+            .map(NearBalance)
+            .map_err(|_| format!("\"{}\" is too large", value))
+    }
+}
+
+pub fn format_near_amount(yocto_near: near_primitives::types::Balance) -> String {
+    const ONE_NEAR: u128 = 10u128.pow(24);
+    let fraction = format!("{:024}", yocto_near % ONE_NEAR);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{} NEAR", yocto_near / ONE_NEAR)
+    } else {
+        format!("{}.{} NEAR", yocto_near / ONE_NEAR, fraction)
+    }
+}
+
+pub fn describe_action(action: &near_primitives::transaction::Action) -> String {
+    match action {
+        near_primitives::transaction::Action::CreateAccount(_) => "Create the receiver account".to_string(),
+        near_primitives::transaction::Action::DeployContract(deploy_contract_action) => {
+            format!("Deploy a contract ({} bytes of code)", deploy_contract_action.code.len())
+        }
+        near_primitives::transaction::Action::FunctionCall(function_call_action) => format!(
+            "Call method `{}` with {} gas and {} attached, arguments: {}",
+            function_call_action.method_name,
+            function_call_action.gas,
+            format_near_amount(function_call_action.deposit),
+            match std::str::from_utf8(&function_call_action.args) {
+                Ok(args) => args.to_string(),
+                Err(_) => near_primitives::serialize::to_base64(&function_call_action.args),
+            }
+        ),
+        near_primitives::transaction::Action::Transfer(transfer_action) => {
+            format!("Transfer {}", format_near_amount(transfer_action.deposit))
+        }
+        near_primitives::transaction::Action::Stake(stake_action) => format!(
+            "Stake {} with the validator key {}",
+            format_near_amount(stake_action.stake),
+            stake_action.public_key
+        ),
+        near_primitives::transaction::Action::AddKey(add_key_action) => {
+            match &add_key_action.access_key.permission {
+                near_primitives::account::AccessKeyPermission::FullAccess => format!(
+                    "Add a FULL ACCESS key {} (nonce {})",
+                    add_key_action.public_key, add_key_action.access_key.nonce
+                ),
+                near_primitives::account::AccessKeyPermission::FunctionCall(function_call_permission) => format!(
+                    "Add a function call access key {} (nonce {}) for calling {} on {} with {}",
+                    add_key_action.public_key,
+                    add_key_action.access_key.nonce,
+                    if function_call_permission.method_names.is_empty() {
+                        "any method".to_string()
+                    } else {
+                        format!("methods [{}]", function_call_permission.method_names.join(", "))
+                    },
+                    function_call_permission.receiver_id,
+                    match function_call_permission.allowance {
+                        Some(allowance) => format!("an allowance of {}", format_near_amount(allowance)),
+                        None => "an unlimited allowance".to_string(),
+                    }
+                ),
+            }
+        }
+        near_primitives::transaction::Action::DeleteKey(delete_key_action) => {
+            format!("Delete the access key {}", delete_key_action.public_key)
+        }
+        near_primitives::transaction::Action::DeleteAccount(delete_account_action) => format!(
+            "DELETE the receiver account and transfer the remaining balance to {}",
+            delete_account_action.beneficiary_id
+        ),
+    }
+}
+
+pub fn print_transaction_summary(
+    transaction: &near_primitives::transaction::Transaction,
+    network_id: Option<NetworkId>,
+) {
+    if let Some(network_id) = network_id {
+        eprintln!("Network:     {}", network_id);
+    };
+    eprintln!("Signer:      {}", transaction.signer_id);
+    eprintln!("Public key:  {}", transaction.public_key);
+    eprintln!("Receiver:    {}", transaction.receiver_id);
+    eprintln!("Nonce:       {}", transaction.nonce);
+    eprintln!("Block hash:  {}", transaction.block_hash);
+    eprintln!("Actions:");
+    for (index, action) in transaction.actions.iter().enumerate() {
+        eprintln!("  {}. {}", index + 1, describe_action(action));
+    }
+    if network_id == Some(NetworkId::Mainnet) {
+        eprintln!();
+        eprintln!("WARNING: this transaction is going to mainnet, where it moves real funds");
+    };
+}
+
+/// The transaction fields as they appear in the JSON output
+pub fn transaction_to_json(transaction: &near_primitives::transaction::Transaction) -> serde_json::Value {
+    serde_json::json!({
+        "signer_id": transaction.signer_id,
+        "public_key": transaction.public_key.to_string(),
+        "nonce": transaction.nonce,
+        "receiver_id": transaction.receiver_id,
+        "block_hash": transaction.block_hash.to_string(),
+        "actions": transaction
+            .actions
+            .iter()
+            .cloned()
+            .map(near_primitives::views::ActionView::from)
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Whatever the transaction pipeline has produced by its last stage. With
+/// `--format json` it is printed as a single document whose fields are always
+/// present (`null` for the stages which have not happened), so scripts can
+/// rely on the schema.
+pub struct TransactionOutput<'a> {
+    pub transaction: &'a near_primitives::transaction::Transaction,
+    pub network_id: Option<NetworkId>,
+    pub encoding: TransactionFormat,
+    pub unsigned_transaction: Option<String>,
+    pub signed_transaction: Option<String>,
+    pub outcome: Option<near_primitives::views::FinalExecutionOutcomeView>,
+}
+
+impl TransactionOutput<'_> {
+    /// Only a transaction which has been sent to a known network can be
+    /// looked up in an explorer
+    fn explorer_url(&self) -> Option<String> {
+        self.outcome.as_ref()?;
+        let explorer_url = self.network_id?.explorer_url()?;
+        Some(format!("{}/transactions/{}", explorer_url, self.transaction.get_hash()))
+    }
+    pub fn print(&self, output_format: OutputFormat) {
+        let encoding: &str = self.encoding.into();
+        let explorer_url = self.explorer_url();
+        match output_format {
+            OutputFormat::Plaintext => {
+                if let Some(ref unsigned_transaction) = self.unsigned_transaction {
+                    println!("Unsigned transaction ({}):\n{}", encoding, unsigned_transaction);
+                };
+                if let Some(ref signed_transaction) = self.signed_transaction {
+                    println!("Signed transaction ({}):\n{}", encoding, signed_transaction);
+                };
+                println!("Transaction hash: {}", self.transaction.get_hash());
+                if let Some(ref outcome) = self.outcome {
+                    println!("Transaction status: {:?}", outcome.status);
+                    println!("Final execution outcome: {:#?}", outcome);
+                };
+                if let Some(ref explorer_url) = explorer_url {
+                    println!("Explorer: {}", explorer_url);
+                };
+            }
+            OutputFormat::Qr => {
+                match (&self.signed_transaction, &self.unsigned_transaction) {
+                    (Some(signed_transaction), _) => {
+                        println!("Signed transaction ({}):", encoding);
+                        print_qr_codes(signed_transaction);
+                    }
+                    (None, Some(unsigned_transaction)) => {
+                        println!("Unsigned transaction ({}):", encoding);
+                        print_qr_codes(unsigned_transaction);
+                    }
+                    (None, None) => {}
+                };
+                println!("Transaction hash: {}", self.transaction.get_hash());
+                if let Some(ref outcome) = self.outcome {
+                    println!("Transaction status: {:?}", outcome.status);
+                };
+                if let Some(ref explorer_url) = explorer_url {
+                    println!("Explorer: {}", explorer_url);
+                };
+            }
+            OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "transaction_hash": self.transaction.get_hash().to_string(),
+                        "transaction": transaction_to_json(self.transaction),
+                        "encoding": encoding,
+                        "unsigned_transaction": self.unsigned_transaction,
+                        "signed_transaction": self.signed_transaction,
+                        "status": self.outcome.as_ref().map(|outcome| &outcome.status),
+                        "final_execution_outcome": self.outcome,
+                        "explorer_url": explorer_url,
+                    }))
+                    .expect("Transaction output is not expected to fail on serialization")
+                );
+            }
+        }
+    }
+    /// A transaction which has been included in a block but failed to execute
+    /// still fails the command
+    pub fn status(&self) -> CliResult {
+        match self.outcome.as_ref().map(|outcome| &outcome.status) {
+            Some(near_primitives::views::FinalExecutionStatus::Failure(err)) => {
+                Err(CliError::RpcError(format!("The transaction has failed: {:?}", err)))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+static HAS_ASKED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// A prompt which cannot read the terminal fails the command instead of
+/// panicking
+fn prompt_error(err: std::io::Error) -> CliError {
+    CliError::IoError(format!("Failed to read from the terminal: {}", err))
+}
+
+/// Every prompt hands its answer over through `answer`, which also records
+/// that the session has asked for something
+pub trait PromptAnswer<T> {
+    fn answer(self) -> Result<T, CliError>;
+}
+
+impl<T> PromptAnswer<T> for std::io::Result<T> {
+    fn answer(self) -> Result<T, CliError> {
+        HAS_ASKED.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.map_err(prompt_error)
+    }
+}
+
+/// Whether any prompt has been answered, i.e. whether the session has been
+/// interactive
+pub fn has_asked() -> bool {
+    HAS_ASKED.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Shows the selection on stderr and returns the chosen index; Esc or `q`
+/// cancels the command
+pub fn interact_select(select: &dialoguer::Select<'_>) -> Result<usize, CliError> {
+    select
+        .interact_on_opt(&dialoguer::console::Term::stderr())
+        .answer()?
+        .ok_or(CliError::UserCancelled)
+}
+
+/// Asks the user to approve the transaction which has just been summarized
+pub fn confirm_signing() -> Result<bool, CliError> {
+    dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+        .with_prompt("Do you want to sign this transaction?")
+        .default(false)
+        .interact_on(&dialoguer::console::Term::stderr())
+        .map_err(|err| CliError::IoError(format!("Failed to read the confirmation: {}", err)))
+}
+
+/// Asks the user to approve sending the transaction which has just been
+/// summarized
+pub fn confirm_broadcasting() -> Result<bool, CliError> {
+    dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+        .with_prompt("Do you want to send this transaction?")
+        .default(false)
+        .interact_on(&dialoguer::console::Term::stderr())
+        .map_err(|err| CliError::IoError(format!("Failed to read the confirmation: {}", err)))
+}
+
+/// Asks which network's keychain to use when there is no connection to tell
+pub fn input_network_id() -> Result<NetworkId, CliError> {
+    eprintln!();
+    let networks = <NetworkId as strum::VariantNames>::VARIANTS;
+    let select_network = interact_select(
+        dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("Which network's keychain holds the key?")
+            .items(&networks)
+            .default(0),
+    )?;
+    match select_network {
+        0 => Ok(NetworkId::Testnet),
+        1 => Ok(NetworkId::Mainnet),
+        2 => Ok(NetworkId::Betanet),
+        3 => Ok(NetworkId::Custom),
+        _ => unreachable!("Error")
+    }
+}
+
+/// An offline transaction starts with an empty public key which the signer
+/// fills in, unless a bundle has already set the key its nonce belongs to;
+/// then no other key may sign it
+pub fn check_offline_public_key(
+    prepopulated_public_key: &near_crypto::PublicKey,
+    signer_public_key: &near_crypto::PublicKey,
+) -> CliResult {
+    let is_placeholder =
+        *prepopulated_public_key == near_crypto::PublicKey::empty(prepopulated_public_key.key_type());
+    if !is_placeholder && prepopulated_public_key != signer_public_key {
+        return Err(CliError::SigningError(format!(
+            "The nonce has been prepared for the key {}, but the transaction is signed with {}",
+            prepopulated_public_key, signer_public_key
+        )));
+    };
+    Ok(())
+}
+
+/// Placeholder which is printed instead of secret keys and seed phrases
+pub const REDACTED_SECRET: &str = "<REDACTED>";
+
+/// Reads a secret without echoing it and wipes the typed text once it is
+/// parsed; invalid input is explained and asked for again
+pub fn input_secret<T>(prompt: &str) -> Result<T, CliError>
+where
+    T: FromStr<Err = String>,
+{
+    loop {
+        let input = zeroize::Zeroizing::new(
+            dialoguer::Password::new()
+                .with_prompt(prompt)
+                .interact()
+                .answer()?,
+        );
+        match T::from_str(&input) {
+            Ok(value) => break Ok(value),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+}
+
+/// A BIP39 seed phrase, wiped from memory when dropped
+#[derive(Clone, PartialEq)]
+pub struct SeedPhrase(zeroize::Zeroizing<String>);
+
+impl SeedPhrase {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for SeedPhrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SeedPhrase").field(&REDACTED_SECRET).finish()
+    }
+}
+
+impl std::str::FromStr for SeedPhrase {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        bip39::Mnemonic::parse(value)
+            .map_err(|err| format!("Invalid seed phrase: {}", err))?;
+        Ok(SeedPhrase(zeroize::Zeroizing::new(value.to_string())))
+    }
+}
+
+fn quote_cli_arg(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:/=@+%^".contains(c));
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// The arguments of the command line which repeats a session. It is partial
+/// when the session went on to a second action: the command line takes a
+/// single action, so it stops at the receiver and the actions are asked for
+/// again
+#[derive(Debug, PartialEq)]
+pub struct CliCommandArgs {
+    pub args: Vec<String>,
+    pub is_partial: bool,
+}
+
+impl CliCommandArgs {
+    pub fn complete(args: Vec<String>) -> Self {
+        CliCommandArgs { args, is_partial: false }
+    }
+    pub fn partial(args: Vec<String>) -> Self {
+        CliCommandArgs { args, is_partial: true }
+    }
+    /// Puts the arguments of the enclosing command in front
+    pub fn prefixed(self, prefix: Vec<String>) -> Self {
+        CliCommandArgs {
+            args: prefix.into_iter().chain(self.args).collect(),
+            .. self
+        }
+    }
+}
+
+/// Prints the command line which repeats the current run (to stderr, so it
+/// does not mix with the command output)
+pub fn print_cli_command(cli_command_args: &CliCommandArgs) {
+    let program = std::env::args()
+        .next()
+        .and_then(|program| {
+            std::path::Path::new(&program)
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string());
+    let cli_command = std::iter::once(program)
+        .chain(cli_command_args.args.iter().map(|arg| quote_cli_arg(arg)))
+        .collect::<Vec<String>>()
+        .join(" ");
+    if cli_command_args.is_partial {
+        eprintln!(
+            "This transaction has several actions and a command takes only one, so this command asks for the actions again:\n{}\n",
+            cli_command
+        );
+    } else {
+        eprintln!("Your console command:\n{}\n", cli_command);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_near_balance_from_str() {
+        assert_eq!(NearBalance::from_str("10 NEAR"), Ok(NearBalance(10 * 10u128.pow(24))));
+        assert_eq!(NearBalance::from_str("0.25NEAR"), Ok(NearBalance(25 * 10u128.pow(22))));
+        assert_eq!(NearBalance::from_str("500 milliNEAR"), Ok(NearBalance(5 * 10u128.pow(23))));
+        assert_eq!(NearBalance::from_str("1000 yoctoNEAR"), Ok(NearBalance(1000)));
+        assert_eq!(NearBalance::from_str("0"), Ok(NearBalance(0)));
+        assert!(NearBalance::from_str("10").is_err());
+        assert!(NearBalance::from_str("10 bitcoin").is_err());
+        assert!(NearBalance::from_str("1.5 yoctoNEAR").is_err());
+        assert!(NearBalance::from_str("1,5 NEAR").is_err());
+        assert!(NearBalance::from_str("1000000000000000 NEAR").is_err());
+    }
+
+    #[test]
+    fn test_valid_account_id_from_str() {
+        for account_id in &[
+            "near",
+            "bob.testnet",
+            "app-1_a.alice.near",
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+        ] {
+            assert!(ValidAccountId::from_str(account_id).is_ok(), "{}", account_id);
+        }
+        for account_id in &["a", "Bob.testnet", "bob..testnet", ".bob", "bob-", "bob testnet", "bob@near"] {
+            assert!(ValidAccountId::from_str(account_id).is_err(), "{}", account_id);
+        }
+        assert!(ValidAccountId::from_str(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_public_key_from_str() {
+        assert!(PublicKey::from_str("ed25519:7FmDRADa1v4BcLiiR9MPPdmWQp3Um1iPdAYATvBY1YzS").is_ok());
+        assert!(PublicKey::from_str("rsa:7FmDRADa1v4BcLiiR9MPPdmWQp3Um1iPdAYATvBY1YzS")
+            .unwrap_err()
+            .contains("unknown prefix"));
+        assert!(PublicKey::from_str("ed25519:7FmDRADa1v4BcLiiR9MPPdmWQp3Um1iPdAYATvBY1Yz0")
+            .unwrap_err()
+            .contains("base58"));
+        assert!(PublicKey::from_str("ed25519:7FmDRADa1v4BcLiiR9MPPdmWQp3Um1iP")
+            .unwrap_err()
+            .contains("32 bytes"));
+    }
+
+    #[test]
+    fn test_near_balance_display() {
+        assert_eq!(NearBalance(10 * 10u128.pow(24)).to_string(), "10 NEAR");
+        assert_eq!(NearBalance(25 * 10u128.pow(22)).to_string(), "0.25 NEAR");
+        assert_eq!(NearBalance::from_str(&NearBalance(1).to_string()), Ok(NearBalance(1)));
+    }
+
+    #[test]
+    fn test_qr_parts_roundtrip() {
+        let payload = "A".repeat(QR_PART_LENGTH * 2) + "BC";
+        let mut parts = split_into_qr_parts(&payload);
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0].starts_with("near-tx:1/3:"));
+        parts.reverse();
+        assert_eq!(join_qr_parts(&parts.join("\n")).unwrap(), payload);
+        assert!(join_qr_parts(&parts[..2].join(" ")).unwrap_err().contains("incomplete"));
+        assert_eq!(split_into_qr_parts("AQID"), vec!["AQID".to_string()]);
+        assert_eq!(join_qr_parts(" AQID\n").unwrap(), "AQID");
+    }
+
+    #[test]
+    fn test_secrets_debug_is_redacted() {
+        let seed_phrase = "fossil olive fault relief inch hip soldier cash coach stomach ensure flush";
+        assert_eq!(
+            format!("{:?}", SeedPhrase::from_str(seed_phrase).unwrap()),
+            format!("SeedPhrase({:?})", REDACTED_SECRET)
+        );
+        let secret_key = SecretKey(near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519));
+        assert_eq!(format!("{:?}", secret_key), format!("SecretKey({:?})", REDACTED_SECRET));
+    }
+
+    #[test]
+    fn test_signature_format_roundtrip() {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let signature = secret_key.sign(b"near message");
+        for format in &[SignatureFormat::Base58, SignatureFormat::Base64] {
+            let encoded = format.encode(&signature);
+            let decoded = format.decode(&encoded, near_crypto::KeyType::ED25519).unwrap();
+            assert_eq!(decoded, signature);
+            assert!(decoded.verify(b"near message", &secret_key.public_key()));
+        }
+        let bare_base58 = signature.to_string().trim_start_matches("ed25519:").to_string();
+        assert_eq!(
+            SignatureFormat::Base58.decode(&bare_base58, near_crypto::KeyType::ED25519).unwrap(),
+            signature
+        );
+        assert!(SignatureFormat::Base64.decode("not base64!", near_crypto::KeyType::ED25519).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use chacha20poly1305::aead::{Aead, NewAead};
+use rand::RngCore;
+use zeroize::Zeroize;
+use std::str::FromStr;
+
+use crate::common::{CliError, NetworkId};
+
+
+/// scrypt cost parameters for newly encrypted keys (N = 2^15, r = 8, p = 1)
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// The key file layout used by near-cli; encrypted keys keep `account_id` and
+/// `public_key` in plain text and replace `private_key` with
+/// `encrypted_private_key`
+#[derive(serde::Serialize, serde::Deserialize)]
+struct KeyFile {
+    account_id: String,
+    public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_private_key: Option<EncryptedPrivateKey>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EncryptedPrivateKey {
+    kdf: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// `~/.near-credentials`, unless `NEAR_CREDENTIALS_DIR` points somewhere else
+pub fn credentials_dir() -> Result<std::path::PathBuf, CliError> {
+    if let Some(credentials_dir) = std::env::var_os("NEAR_CREDENTIALS_DIR") {
+        return Ok(std::path::PathBuf::from(credentials_dir));
+    };
+    dirs::home_dir()
+        .map(|home_dir| home_dir.join(".near-credentials"))
+        .ok_or_else(|| CliError::IoError("Failed to find the home directory".to_string()))
+}
+
+pub fn key_file_path(
+    network_id: NetworkId,
+    account_id: &str,
+) -> Result<std::path::PathBuf, CliError> {
+    Ok(credentials_dir()?
+        .join(network_id.to_string())
+        .join(format!("{}.json", account_id)))
+}
+
+/// Loads the signing key of the account; `input_passphrase` is only called
+/// when the key is stored encrypted
+pub fn load_secret_key(
+    network_id: NetworkId,
+    account_id: &str,
+    input_passphrase: impl FnOnce() -> Result<zeroize::Zeroizing<String>, CliError>,
+) -> Result<crate::common::SecretKey, CliError> {
+    let key_file_path = key_file_path(network_id, account_id)?;
+    log::info!("Loading the key of {} from {}", account_id, key_file_path.display());
+    let key_file_content = std::fs::read_to_string(&key_file_path)
+        .map(zeroize::Zeroizing::new)
+        .map_err(|err| {
+            CliError::IoError(format!(
+                "There is no key for {} on {} in the keychain ({}): {}",
+                account_id,
+                network_id,
+                key_file_path.display(),
+                err
+            ))
+        })?;
+    let key_file: KeyFile = serde_json::from_str(&key_file_content).map_err(|err| {
+        CliError::ParseError(format!("{} is not a valid key file: {}", key_file_path.display(), err))
+    })?;
+    let public_key = near_crypto::PublicKey::from_str(&key_file.public_key).map_err(|err| {
+        CliError::ParseError(format!("{} contains an invalid public key: {:?}", key_file_path.display(), err))
+    })?;
+    let private_key = match (key_file.private_key, key_file.encrypted_private_key) {
+        (Some(private_key), _) => zeroize::Zeroizing::new(private_key),
+        (None, Some(encrypted_private_key)) => {
+            decrypt_private_key(&encrypted_private_key, &input_passphrase()?)?
+        }
+        (None, None) => {
+            return Err(CliError::ParseError(format!(
+                "{} contains neither a private key nor an encrypted one",
+                key_file_path.display()
+            )))
+        }
+    };
+    let secret_key = near_crypto::SecretKey::from_str(&private_key).map_err(|err| {
+        CliError::ParseError(format!("{} contains an invalid private key: {:?}", key_file_path.display(), err))
+    })?;
+    if secret_key.public_key() != public_key {
+        return Err(CliError::ParseError(format!(
+            "The private key in {} does not belong to its public key {}",
+            key_file_path.display(),
+            public_key
+        )));
+    };
+    Ok(crate::common::SecretKey::from(secret_key))
+}
+
+/// Stores the signing key of the account, encrypted when a passphrase is given;
+/// an existing key of the account is only replaced when `overwrite` is set
+pub fn save_secret_key(
+    network_id: NetworkId,
+    account_id: &str,
+    secret_key: &near_crypto::SecretKey,
+    passphrase: Option<&str>,
+    overwrite: bool,
+) -> Result<std::path::PathBuf, CliError> {
+    let key_file_path = key_file_path(network_id, account_id)?;
+    let key_file = match passphrase {
+        Some(passphrase) => KeyFile {
+            account_id: account_id.to_string(),
+            public_key: secret_key.public_key().to_string(),
+            private_key: None,
+            encrypted_private_key: Some(encrypt_private_key(
+                &zeroize::Zeroizing::new(secret_key.to_string()),
+                passphrase,
+            )?),
+        },
+        None => KeyFile {
+            account_id: account_id.to_string(),
+            public_key: secret_key.public_key().to_string(),
+            private_key: Some(secret_key.to_string()),
+            encrypted_private_key: None,
+        },
+    };
+    let key_file_content = serde_json::to_string_pretty(&key_file)
+        .expect("Key file is not expected to fail on serialization");
+    if let Some(key_file_dir) = key_file_path.parent() {
+        std::fs::create_dir_all(key_file_dir).map_err(|err| {
+            CliError::IoError(format!("Failed to create {}: {}", key_file_dir.display(), err))
+        })?;
+    };
+    write_private_file(&key_file_path, &key_file_content, overwrite).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::AlreadyExists {
+            CliError::IoError(format!(
+                "There is already a key for {} on {} in the keychain ({}), use --force to replace it",
+                account_id,
+                network_id,
+                key_file_path.display()
+            ))
+        } else {
+            CliError::IoError(format!("Failed to write {}: {}", key_file_path.display(), err))
+        }
+    })?;
+    Ok(key_file_path)
+}
+
+/// Fails with `AlreadyExists` instead of replacing the file unless `overwrite`
+/// is set
+#[cfg(unix)]
+fn write_private_file(path: &std::path::Path, content: &str, overwrite: bool) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(overwrite)
+        .create_new(!overwrite)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(content.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private_file(path: &std::path::Path, content: &str, overwrite: bool) -> std::io::Result<()> {
+    use std::io::Write;
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(overwrite)
+        .create_new(!overwrite)
+        .truncate(true)
+        .open(path)?
+        .write_all(content.as_bytes())
+}
+
+fn derive_encryption_key(
+    passphrase: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<zeroize::Zeroizing<[u8; 32]>, CliError> {
+    let params = scrypt::ScryptParams::new(log_n, r, p)
+        .map_err(|err| CliError::SigningError(format!("Invalid scrypt parameters: {}", err)))?;
+    let mut encryption_key = zeroize::Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, encryption_key.as_mut())
+        .map_err(|err| CliError::SigningError(format!("Key derivation failed: {}", err)))?;
+    Ok(encryption_key)
+}
+
+fn encrypt_private_key(private_key: &str, passphrase: &str) -> Result<EncryptedPrivateKey, CliError> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let encryption_key = derive_encryption_key(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+    let cipher = chacha20poly1305::ChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(encryption_key.as_ref()));
+    let ciphertext = cipher
+        .encrypt(chacha20poly1305::Nonce::from_slice(&nonce), private_key.as_bytes())
+        .map_err(|_| CliError::SigningError("Failed to encrypt the private key".to_string()))?;
+    Ok(EncryptedPrivateKey {
+        kdf: "scrypt".to_string(),
+        log_n: SCRYPT_LOG_N,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn decrypt_private_key(
+    encrypted_private_key: &EncryptedPrivateKey,
+    passphrase: &str,
+) -> Result<zeroize::Zeroizing<String>, CliError> {
+    if encrypted_private_key.kdf != "scrypt" {
+        return Err(CliError::ParseError(format!(
+            "Unsupported key derivation function \"{}\"",
+            encrypted_private_key.kdf
+        )));
+    };
+    let decode = |field: &str, value: &str| {
+        hex::decode(value)
+            .map_err(|err| CliError::ParseError(format!("The encrypted key has an invalid {}: {}", field, err)))
+    };
+    let salt = decode("salt", &encrypted_private_key.salt)?;
+    let nonce = decode("nonce", &encrypted_private_key.nonce)?;
+    let ciphertext = decode("ciphertext", &encrypted_private_key.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(CliError::ParseError("The encrypted key has an invalid nonce length".to_string()));
+    };
+    let encryption_key = derive_encryption_key(
+        passphrase,
+        &salt,
+        encrypted_private_key.log_n,
+        encrypted_private_key.r,
+        encrypted_private_key.p,
+    )?;
+    let cipher = chacha20poly1305::ChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(encryption_key.as_ref()));
+    let private_key = cipher
+        .decrypt(chacha20poly1305::Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| CliError::SigningError("Wrong passphrase for the encrypted key".to_string()))?;
+    String::from_utf8(private_key)
+        .map(zeroize::Zeroizing::new)
+        .map_err(|err| {
+            let mut private_key = err.into_bytes();
+            private_key.zeroize();
+            CliError::ParseError("The decrypted private key is not valid UTF-8".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_private_key_roundtrip() {
+        let private_key = "ed25519:3D4YudUahN1nawWogh8pAKSj92sUNMdbZGjn7kERKzYoTy8tnFQuwoGUC51DowKqorvkr2pytJSnwuSbsNVfqygr";
+        let encrypted_private_key = encrypt_private_key(private_key, "correct horse").unwrap();
+        assert_eq!(decrypt_private_key(&encrypted_private_key, "correct horse").unwrap().as_str(), private_key);
+        assert!(decrypt_private_key(&encrypted_private_key, "wrong horse").is_err());
+    }
+
+    #[test]
+    fn test_write_private_file_overwrite() {
+        let path = std::env::temp_dir().join(format!("near-cli-keystore-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        write_private_file(&path, "first", false).unwrap();
+        assert_eq!(
+            write_private_file(&path, "second", false).unwrap_err().kind(),
+            std::io::ErrorKind::AlreadyExists
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+        write_private_file(&path, "second", true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
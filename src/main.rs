@@ -1,6 +1,9 @@
 use structopt::StructOpt;
+use strum::VariantNames;
 
+pub(crate) mod bundle;
 pub(crate) mod common;
+pub(crate) mod keystore;
 pub(crate) mod utils_subcommand;
 mod consts;
 mod command;
@@ -12,11 +15,22 @@ use command::{
 
 #[derive(Debug)]
 struct Args {
+    output_format: crate::common::OutputFormat,
     subcommand: ArgsCommand,
 }
 
 #[derive(Debug, Default, StructOpt)]
 struct CliArgs {
+    /// Log what is going on to stderr (-v for info, -vv for debug, -vvv for
+    /// trace); RUST_LOG takes precedence when it is set
+    #[structopt(short, long, parse(from_occurrences), global = true)]
+    verbose: u8,
+    // No default_value: clap lets the default of a subcommand override a
+    // global value which has been given before it
+    /// How the result is printed (plaintext by default); the JSON layout is
+    /// stable for scripts, QR codes are only available for transactions
+    #[structopt(long, possible_values = crate::common::OutputFormat::VARIANTS, global = true)]
+    format: Option<crate::common::OutputFormat>,
     #[structopt(subcommand)]
     subcommand: Option<CliCommand>,
 }
@@ -25,16 +39,27 @@ impl From<CliArgs> for Args {
     fn from(item: CliArgs) -> Self {
         let subcommand = match item.subcommand {
             Some(cli_subcommand) => ArgsCommand::from(cli_subcommand),
-            None => ArgsCommand::choose_command(),
+            None => ArgsCommand::choose_command().unwrap_or_else(|err| err.exit()),
         };
         Self {
+            output_format: item.format.unwrap_or_default(),
             subcommand,
         }
     }
 }
 
 impl Args {
-    async fn process(self) -> String {
+    async fn process(self) -> crate::common::CliResult {
+        if crate::common::has_asked() {
+            let format_args = match self.output_format {
+                crate::common::OutputFormat::Plaintext => vec![],
+                output_format => {
+                    let output_format: &str = output_format.into();
+                    vec!["--format".to_string(), output_format.to_string()]
+                }
+            };
+            crate::common::print_cli_command(&self.subcommand.to_cli_args().prefixed(format_args));
+        };
         match self.subcommand {
             ArgsCommand::ConstructTransactionCommand(mode) => {
                 let unsigned_transaction = near_primitives::transaction::Transaction {
@@ -45,19 +70,44 @@ impl Args {
                     block_hash: Default::default(),
                     actions: vec![],
                 };
-                mode.process(unsigned_transaction).await;
+                mode.process(unsigned_transaction, self.output_format).await
             },
-            _ => unreachable!("Error") 
-        };
-        "Ok".to_string()
+            ArgsCommand::Utils(utils) => {
+                utils.process(self.output_format).await
+            },
+            ArgsCommand::SendSignedTransactionCommand(send_signed_transaction) => {
+                send_signed_transaction.process(self.output_format).await
+            },
+            ArgsCommand::SignUnsignedTransactionCommand(sign_unsigned_transaction) => {
+                sign_unsigned_transaction.process(self.output_format).await
+            },
+        }
     }
 }
 
+fn init_logger(verbose: u8) {
+    let level = match verbose {
+        0 => "off",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    env_logger::Builder::from_env(
+        env_logger::Env::default().default_filter_or(format!("{}={}", module_path!(), level)),
+    )
+    .target(env_logger::Target::Stderr)
+    .init();
+}
+
 fn main() {
     let cli = CliArgs::from_args();
+    init_logger(cli.verbose);
     let args = Args::from(cli);
 
-    actix::System::builder()
-    .build()
-    .block_on(async move { args.process().await });
+    let result = actix::System::builder()
+        .build()
+        .block_on(async move { args.process().await });
+    if let Err(err) = result {
+        err.exit()
+    }
 }
@@ -0,0 +1,206 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
+
+
+/// Look inside an encoded transaction, signed or unsigned (the signature is
+/// verified against the public key of the transaction)
+#[derive(Debug)]
+pub struct DecodeTransaction {
+    pub transaction: DecodedTransaction,
+}
+
+#[derive(Debug)]
+pub enum DecodedTransaction {
+    Unsigned(near_primitives::transaction::Transaction),
+    Signed(near_primitives::transaction::SignedTransaction),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDecodeTransaction {
+    /// The encoded transaction ("-" to read it from stdin)
+    transaction: Option<String>,
+    #[structopt(long, parse(from_os_str), conflicts_with = "transaction")]
+    file: Option<std::path::PathBuf>,
+    #[structopt(long, default_value = "base64", possible_values = crate::common::TransactionFormat::VARIANTS)]
+    encoding: crate::common::TransactionFormat,
+}
+
+impl From<CliDecodeTransaction> for DecodeTransaction {
+    fn from(item: CliDecodeTransaction) -> Self {
+        let encoded_transaction: Option<String> = match (item.transaction, item.file) {
+            (Some(cli_transaction), _) if cli_transaction == "-" => {
+                Some(crate::common::read_encoded_transaction(std::path::Path::new("-"))
+                    .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit()))
+            },
+            (Some(cli_transaction), _) => Some(cli_transaction),
+            (None, Some(cli_file)) => Some(crate::common::read_encoded_transaction(&cli_file)
+                .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit())),
+            (None, None) => None
+        };
+        let transaction = match encoded_transaction {
+            Some(encoded_transaction) => {
+                DecodeTransaction::decode_transaction(&encoded_transaction, item.encoding)
+                    .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit())
+            },
+            None => DecodeTransaction::input_transaction().unwrap_or_else(|err| err.exit())
+        };
+        DecodeTransaction {
+            transaction,
+        }
+    }
+}
+
+impl DecodeTransaction {
+    pub fn choose_decode_transaction() -> Result<Self, crate::common::CliError> {
+        Ok(DecodeTransaction {
+            transaction: DecodeTransaction::input_transaction()?,
+        })
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format = crate::common::TransactionFormat::Base64;
+        let format_str: &str = format.into();
+        let serialized_transaction = match &self.transaction {
+            DecodedTransaction::Unsigned(transaction) => transaction.try_to_vec(),
+            DecodedTransaction::Signed(signed_transaction) => signed_transaction.try_to_vec(),
+        }
+        .expect("Transaction is not expected to fail on serialization");
+        vec![
+            "--encoding".to_string(),
+            format_str.to_string(),
+            format.encode(&serialized_transaction),
+        ]
+    }
+    pub fn input_transaction() -> Result<DecodedTransaction, crate::common::CliError> {
+        eprintln!();
+        let formats = crate::common::TransactionFormat::VARIANTS;
+        let select_format = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the encoding of the transaction")
+                .items(&formats)
+                .default(0),
+        )?;
+        let format = match select_format {
+            0 => crate::common::TransactionFormat::Base64,
+            1 => crate::common::TransactionFormat::Hex,
+            _ => unreachable!("Error")
+        };
+        loop {
+            let input: String = Input::new()
+                .with_prompt("Enter the transaction")
+                .interact_text()
+                .answer()?;
+            match DecodeTransaction::decode_transaction(&input, format) {
+                Ok(transaction) => break Ok(transaction),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    /// A signed transaction is an unsigned one followed by the signature, so
+    /// the signed layout is tried first
+    fn decode_transaction(
+        encoded_transaction: &str,
+        format: crate::common::TransactionFormat,
+    ) -> Result<DecodedTransaction, String> {
+        let serialized_transaction = format.decode(encoded_transaction)?;
+        if let Ok(signed_transaction) =
+            near_primitives::transaction::SignedTransaction::try_from_slice(&serialized_transaction)
+        {
+            return Ok(DecodedTransaction::Signed(signed_transaction));
+        };
+        near_primitives::transaction::Transaction::try_from_slice(&serialized_transaction)
+            .map(DecodedTransaction::Unsigned)
+            .map_err(|err| format!("The input is neither a signed nor an unsigned transaction: {}", err))
+    }
+    /// Function call arguments are usually JSON, which is worth showing as such
+    fn decode_function_call_args(action: &near_primitives::transaction::Action) -> Option<serde_json::Value> {
+        match action {
+            near_primitives::transaction::Action::FunctionCall(function_call_action) => {
+                serde_json::from_slice(&function_call_action.args).ok()
+            }
+            _ => None,
+        }
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("decode-transaction")?;
+        let (transaction, signature) = match &self.transaction {
+            DecodedTransaction::Unsigned(transaction) => (transaction, None),
+            DecodedTransaction::Signed(signed_transaction) => {
+                (&signed_transaction.transaction, Some(&signed_transaction.signature))
+            }
+        };
+        let transaction_hash = transaction.get_hash();
+        let is_signature_valid = signature
+            .map(|signature| signature.verify(transaction_hash.as_ref(), &transaction.public_key));
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "Kind:              {}",
+                    if signature.is_some() { "signed transaction" } else { "unsigned transaction" }
+                );
+                println!("Transaction hash:  {}", transaction_hash);
+                println!("Signer:            {}", transaction.signer_id);
+                println!("Public key:        {}", transaction.public_key);
+                println!("Receiver:          {}", transaction.receiver_id);
+                println!("Nonce:             {}", transaction.nonce);
+                println!("Block hash:        {}", transaction.block_hash);
+                if let (Some(signature), Some(is_signature_valid)) = (signature, is_signature_valid) {
+                    println!("Signature:         {}", signature);
+                    println!(
+                        "Signature check:   {}",
+                        if is_signature_valid {
+                            "valid for the public key of the transaction"
+                        } else {
+                            "INVALID for the public key of the transaction"
+                        }
+                    );
+                };
+                println!("Actions:");
+                for (index, action) in transaction.actions.iter().enumerate() {
+                    println!("  {}. {}", index + 1, crate::common::describe_action(action));
+                    if let Some(args) = DecodeTransaction::decode_function_call_args(action) {
+                        let args = serde_json::to_string_pretty(&args)
+                            .expect("JSON value is not expected to fail on serialization");
+                        for line in args.lines() {
+                            println!("       {}", line);
+                        }
+                    };
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "kind": if signature.is_some() { "signed" } else { "unsigned" },
+                        "transaction_hash": transaction_hash.to_string(),
+                        "transaction": crate::common::transaction_to_json(transaction),
+                        "actions_description": transaction
+                            .actions
+                            .iter()
+                            .map(|action| serde_json::json!({
+                                "description": crate::common::describe_action(action),
+                                "function_call_args": DecodeTransaction::decode_function_call_args(action),
+                            }))
+                            .collect::<Vec<_>>(),
+                        "signature": signature.map(|signature| signature.to_string()),
+                        "signature_valid": is_signature_valid,
+                    }))
+                    .expect("Decoded transaction output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        if is_signature_valid == Some(false) {
+            return Err(crate::common::CliError::SigningError(
+                "The signature does not match the public key of the transaction".to_string(),
+            ));
+        };
+        Ok(())
+    }
+}
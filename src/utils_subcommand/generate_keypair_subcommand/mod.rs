@@ -1,33 +1,20 @@
 use std::str::FromStr;
+use structopt::StructOpt;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
 
 
-fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
-    const HARDEND: u32 = 1 << 31;
-
-    format!(
-        "m/{}",
-        (0..bip32path.depth())
-            .map(|index| {
-                let value = *bip32path.index(index).unwrap();
-                if value < HARDEND {
-                    value.to_string()
-                } else {
-                    format!("{}'", value - HARDEND)
-                }
-            })
-            .collect::<Vec<String>>()
-            .join("/")
-    )
-}
-
 /// Generate a key pair of secret and public keys (use it anywhere you need
 /// Ed25519 keys)
 #[derive(Debug)]
 pub struct GenerateKeypair {
-    pub master_seed_phrase: Option<String>,
+    pub master_seed_phrase: Option<crate::common::SeedPhrase>,
     pub new_master_seed_phrase_words_count: usize,
     pub seed_phrase_hd_path: slip10::BIP32Path,
-    pub format: crate::common::OutputFormat,
 }
 
 impl Default for GenerateKeypair {
@@ -36,75 +23,154 @@ impl Default for GenerateKeypair {
             master_seed_phrase: None,
             new_master_seed_phrase_words_count: 12,
             seed_phrase_hd_path: slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap(),
-            format: crate::common::OutputFormat::Json
         }
     }
 }
 
-impl GenerateKeypair {
-    pub async fn process(self) -> String {
-        let (master_seed_phrase, master_seed) =
-            if let Some(ref master_seed_phrase) = self.master_seed_phrase {
-                (
-                    master_seed_phrase.clone(),
-                    bip39::Mnemonic::parse(master_seed_phrase).unwrap().to_seed(""),
-                )
-            } else {
-                let mnemonic = bip39::Mnemonic::generate(self.new_master_seed_phrase_words_count).unwrap();
-                (mnemonic.as_str().to_owned(), mnemonic.to_seed(""))
-            };
+#[derive(Debug, StructOpt)]
+pub struct CliGenerateKeypair {
+    #[structopt(long)]
+    master_seed_phrase: Option<crate::common::SeedPhrase>,
+    #[structopt(long, default_value = "12", possible_values = &["12", "15", "18", "21", "24"])]
+    new_master_seed_phrase_words_count: usize,
+    #[structopt(long, default_value = "m/44'/397'/0'")]
+    seed_phrase_hd_path: slip10::BIP32Path,
+}
 
-        let derived_private_key = slip10::derive_key_from_path(
-            &master_seed,
-            slip10::Curve::Ed25519,
+impl From<CliGenerateKeypair> for GenerateKeypair {
+    fn from(item: CliGenerateKeypair) -> Self {
+        GenerateKeypair {
+            master_seed_phrase: item.master_seed_phrase,
+            new_master_seed_phrase_words_count: item.new_master_seed_phrase_words_count,
+            seed_phrase_hd_path: item.seed_phrase_hd_path,
+        }
+    }
+}
+
+impl GenerateKeypair {
+    pub fn choose_generate_keypair() -> Result<Self, crate::common::CliError> {
+        let master_seed_phrase: Option<crate::common::SeedPhrase> = GenerateKeypair::input_master_seed_phrase()?;
+        let new_master_seed_phrase_words_count: usize = match master_seed_phrase {
+            Some(_) => GenerateKeypair::default().new_master_seed_phrase_words_count,
+            None => GenerateKeypair::input_new_master_seed_phrase_words_count()?
+        };
+        let seed_phrase_hd_path: slip10::BIP32Path = GenerateKeypair::input_seed_phrase_hd_path()?;
+        Ok(GenerateKeypair {
+            master_seed_phrase,
+            new_master_seed_phrase_words_count,
+            seed_phrase_hd_path,
+        })
+    }
+    pub fn input_master_seed_phrase() -> Result<Option<crate::common::SeedPhrase>, crate::common::CliError> {
+        eprintln!();
+        let choose_input = vec![
+            "Yes, I want to generate a new master seed phrase",
+            "No, I want to derive the key pair from my existing master seed phrase",
+        ];
+        let select_choose_input = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want to generate a new master seed phrase?")
+                .items(&choose_input)
+                .default(0),
+        )?;
+        match select_choose_input {
+            0 => Ok(None),
+            1 => Ok(Some(crate::common::input_secret("Enter the master seed phrase")?)),
+            _ => unreachable!("Error")
+        }
+    }
+    pub fn input_new_master_seed_phrase_words_count() -> Result<usize, crate::common::CliError> {
+        Input::new()
+            .with_prompt("How many words should the new master seed phrase have? (12, 15, 18, 21 or 24)")
+            .default(12)
+            .validate_with(|input: &usize| -> Result<(), &str> {
+                match input {
+                    12 | 15 | 18 | 21 | 24 => Ok(()),
+                    _ => Err("The number of words must be one of 12, 15, 18, 21 or 24")
+                }
+            })
+            .interact_text()
+            .answer()
+    }
+    pub fn input_seed_phrase_hd_path() -> Result<slip10::BIP32Path, crate::common::CliError> {
+        let input: String = Input::new()
+            .with_prompt("Enter the seed phrase HD path")
+            .default("m/44'/397'/0'".to_string())
+            .validate_with(|input: &String| -> Result<(), String> {
+                slip10::BIP32Path::from_str(input)
+                    .map(|_| ())
+                    .map_err(|err| format!("Invalid HD path: {:?}", err))
+            })
+            .interact_text()
+            .answer()?;
+        slip10::BIP32Path::from_str(&input)
+            .map_err(|err| crate::common::CliError::ParseError(format!("Invalid HD path: {:?}", err)))
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = match self.master_seed_phrase {
+            Some(_) => vec![
+                "--master-seed-phrase".to_string(),
+                crate::common::REDACTED_SECRET.to_string(),
+            ],
+            None => vec![
+                "--new-master-seed-phrase-words-count".to_string(),
+                self.new_master_seed_phrase_words_count.to_string(),
+            ],
+        };
+        args.extend(vec![
+            "--seed-phrase-hd-path".to_string(),
+            crate::common::bip32path_to_string(&self.seed_phrase_hd_path),
+        ]);
+        args
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("generate-keypair")?;
+        let master_seed_phrase = match self.master_seed_phrase {
+            Some(ref master_seed_phrase) => zeroize::Zeroizing::new(master_seed_phrase.as_str().to_owned()),
+            None => {
+                let mnemonic = bip39::Mnemonic::generate(self.new_master_seed_phrase_words_count)
+                    .map_err(|err| crate::common::CliError::SigningError(format!("Seed phrase generation failed: {}", err)))?;
+                zeroize::Zeroizing::new(mnemonic.as_str().to_owned())
+            }
+        };
+        let secret_key = crate::common::get_secret_key_from_seed_phrase(
+            master_seed_phrase.as_str(),
             &self.seed_phrase_hd_path,
         )
-        .map_err(|err| {
-            // color_eyre::Report::msg(format!("Key derivation from path failed: {:?}", err))
-            println!("Error: {:?}", &err);
-        })
-        .unwrap();
-
-        let secret_keypair = {
-            let secret = ed25519_dalek::SecretKey::from_bytes(&derived_private_key.key).unwrap();
-            let public = ed25519_dalek::PublicKey::from(&secret);
-            ed25519_dalek::Keypair { secret, public }
+        .map_err(crate::common::CliError::ParseError)?;
+        let public_key = secret_key.public_key();
+        let implicit_account_id = match public_key {
+            near_crypto::PublicKey::ED25519(ref public_key) => hex::encode(public_key.0),
+            _ => unreachable!("Seed phrases only derive ed25519 keys")
         };
+        let public_key_str = public_key.to_string();
+        let secret_keypair_str = zeroize::Zeroizing::new(secret_key.to_string());
 
-        let implicit_account_id = hex::encode(&secret_keypair.public);
-        let public_key_str = format!(
-            "ed25519:{}",
-            bs58::encode(&secret_keypair.public).into_string()
-        );
-        let secret_keypair_str = format!(
-            "ed25519:{}",
-            bs58::encode(secret_keypair.to_bytes()).into_string()
-        );
-
-        match self.format {
+        match output_format {
             crate::common::OutputFormat::Plaintext => {
                 println!(
                     "Master Seed Phrase: {}\nSeed Phrase HD Path: {}\nImplicit Account ID: {}\nPublic Key: {}\nSECRET KEYPAIR: {}",
-                    master_seed_phrase,
-                    bip32path_to_string(&self.seed_phrase_hd_path),
+                    master_seed_phrase.as_str(),
+                    crate::common::bip32path_to_string(&self.seed_phrase_hd_path),
                     implicit_account_id,
                     public_key_str,
-                    secret_keypair_str,
+                    secret_keypair_str.as_str(),
                 );
             }
             crate::common::OutputFormat::Json => {
                 println!(
                     "{}",
                     serde_json::json!({
-                        "master_seed_phrase": master_seed_phrase,
-                        "seed_phrase_hd_path": bip32path_to_string(&self.seed_phrase_hd_path),
+                        "master_seed_phrase": master_seed_phrase.as_str(),
+                        "seed_phrase_hd_path": crate::common::bip32path_to_string(&self.seed_phrase_hd_path),
                         "account_id": implicit_account_id,
                         "public_key": public_key_str,
-                        "private_key": secret_keypair_str,
+                        "private_key": secret_keypair_str.as_str(),
                     })
                 );
             }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
         };
-        public_key_str
+        Ok(())
     }
 }
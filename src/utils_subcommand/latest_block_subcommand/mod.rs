@@ -0,0 +1,75 @@
+use structopt::StructOpt;
+
+use crate::command::select_network::{
+    CliSelectNetwork,
+    choose_connection_config,
+};
+
+
+/// View the latest final block (its hash is the recent block hash offline
+/// signing needs)
+#[derive(Debug)]
+pub struct LatestBlock {
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliLatestBlock {
+    #[structopt(subcommand)]
+    selected_network: Option<CliSelectNetwork>,
+}
+
+impl From<CliLatestBlock> for LatestBlock {
+    fn from(item: CliLatestBlock) -> Self {
+        let connection_config = match item.selected_network {
+            Some(cli_selected_network) => crate::common::ConnectionConfig::from(cli_selected_network),
+            None => choose_connection_config().unwrap_or_else(|err| err.exit())
+        };
+        LatestBlock {
+            connection_config,
+        }
+    }
+}
+
+impl LatestBlock {
+    pub fn choose_latest_block() -> Result<Self, crate::common::CliError> {
+        Ok(LatestBlock {
+            connection_config: choose_connection_config()?,
+        })
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.connection_config.to_cli_args()
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("latest-block")?;
+        log::info!("Fetching the latest final block from {}", self.connection_config.url);
+        let block = self.connection_config
+            .rpc_client()
+            .block(near_primitives::types::BlockReference::Finality(near_primitives::types::Finality::Final))
+            .await
+            .map_err(|err| {
+                crate::common::CliError::RpcError(format!("Failed to fetch the latest block: {:?}", err))
+            })?;
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("Block height:  {}", block.header.height);
+                println!("Block hash:    {}", block.header.hash);
+                println!();
+                println!("Offline transaction arguments:");
+                println!("--block-hash {}", block.header.hash);
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "block_height": block.header.height,
+                        "block_hash": block.header.hash.to_string(),
+                    }))
+                    .expect("Block output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        Ok(())
+    }
+}
@@ -1,27 +1,213 @@
+use structopt::StructOpt;
+use strum_macros::{
+    EnumVariantNames,
+};
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    theme::ColorfulTheme,
+};
+
 pub mod generate_keypair_subcommand;
+use generate_keypair_subcommand::{
+    GenerateKeypair,
+    CliGenerateKeypair
+};
+pub mod save_key_subcommand;
+use save_key_subcommand::{
+    SaveKey,
+    CliSaveKey
+};
+pub mod view_access_key_subcommand;
+use view_access_key_subcommand::{
+    ViewAccessKey,
+    CliViewAccessKey
+};
+pub mod latest_block_subcommand;
+use latest_block_subcommand::{
+    LatestBlock,
+    CliLatestBlock
+};
+pub mod decode_transaction_subcommand;
+use decode_transaction_subcommand::{
+    DecodeTransaction,
+    CliDecodeTransaction
+};
+pub mod sign_message_subcommand;
+use sign_message_subcommand::{
+    SignMessage,
+    CliSignMessage
+};
+pub mod verify_signature_subcommand;
+use verify_signature_subcommand::{
+    VerifySignature,
+    CliVerifySignature
+};
+
 
 /// Collection of various low-level helpers
+#[derive(Debug, StructOpt)]
+pub struct CliUtils {
+    #[structopt(subcommand)]
+    pub util: Option<CliUtil>,
+}
+
 #[derive(Debug)]
-pub struct CliArgs {
-    subcommand: CliSubCommand,
+pub struct Utils {
+    pub util: Util,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum CliUtil {
+    /// Generate a key pair of secret and public keys (use it anywhere you need
+    /// Ed25519 keys)
+    GenerateKeypair(CliGenerateKeypair),
+    /// Store a secret key in the local keychain, optionally encrypted with a
+    /// passphrase
+    SaveKey(CliSaveKey),
+    /// View the access key of an account, including the nonce for the next
+    /// transaction (prints the --nonce and --block-hash for offline signing)
+    ViewAccessKey(CliViewAccessKey),
+    /// View the latest final block (prints the --block-hash for offline
+    /// signing)
+    LatestBlock(CliLatestBlock),
+    /// Look inside an encoded transaction, signed or unsigned (the signature is
+    /// verified against the public key of the transaction)
+    DecodeTransaction(CliDecodeTransaction),
+    /// Sign an arbitrary message with a secret key or a seed phrase (for
+    /// off-chain authentication)
+    SignMessage(CliSignMessage),
+    /// Check a signature of an arbitrary message against a public key
+    VerifySignature(CliVerifySignature),
+}
+
+#[derive(Debug, EnumVariantNames)]
+pub enum Util {
+    GenerateKeypair(GenerateKeypair),
+    SaveKey(SaveKey),
+    ViewAccessKey(ViewAccessKey),
+    LatestBlock(LatestBlock),
+    DecodeTransaction(DecodeTransaction),
+    SignMessage(SignMessage),
+    VerifySignature(VerifySignature),
 }
 
-impl CliArgs {
-    fn rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client("https://rpc.testnet.near.org")
+impl From<CliUtils> for Utils {
+    fn from(item: CliUtils) -> Self {
+        let util = match item.util {
+            Some(cli_util) => Util::from(cli_util),
+            None => Util::choose_util().unwrap_or_else(|err| err.exit())
+        };
+        Self { util }
     }
 }
 
-#[derive(Debug)]
-pub enum CliSubCommand {
-    GenerateKeypair(generate_keypair_subcommand::GenerateKeypair),
+impl From<CliUtil> for Util {
+    fn from(item: CliUtil) -> Self {
+        match item {
+            CliUtil::GenerateKeypair(cli_generate_keypair) => {
+                let generate_keypair: GenerateKeypair = GenerateKeypair::from(cli_generate_keypair);
+                Util::GenerateKeypair(generate_keypair)
+            },
+            CliUtil::SaveKey(cli_save_key) => {
+                let save_key: SaveKey = SaveKey::from(cli_save_key);
+                Util::SaveKey(save_key)
+            },
+            CliUtil::ViewAccessKey(cli_view_access_key) => {
+                let view_access_key: ViewAccessKey = ViewAccessKey::from(cli_view_access_key);
+                Util::ViewAccessKey(view_access_key)
+            },
+            CliUtil::LatestBlock(cli_latest_block) => {
+                let latest_block: LatestBlock = LatestBlock::from(cli_latest_block);
+                Util::LatestBlock(latest_block)
+            },
+            CliUtil::DecodeTransaction(cli_decode_transaction) => {
+                let decode_transaction: DecodeTransaction = DecodeTransaction::from(cli_decode_transaction);
+                Util::DecodeTransaction(decode_transaction)
+            },
+            CliUtil::SignMessage(cli_sign_message) => {
+                let sign_message: SignMessage = SignMessage::from(cli_sign_message);
+                Util::SignMessage(sign_message)
+            },
+            CliUtil::VerifySignature(cli_verify_signature) => {
+                let verify_signature: VerifySignature = VerifySignature::from(cli_verify_signature);
+                Util::VerifySignature(verify_signature)
+            }
+        }
+    }
 }
 
-impl CliArgs {
-    pub async fn process(self) -> String {
-        match self.subcommand {
-            CliSubCommand::GenerateKeypair(generate_keypair_subcommand) => {
-                generate_keypair_subcommand.process().await
+impl Util {
+    pub fn choose_util() -> Result<Self, crate::common::CliError> {
+        eprintln!();
+        let utils = Util::VARIANTS;
+        let select_util = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Choose a util")
+                .items(&utils)
+                .default(0),
+        )?;
+        match select_util {
+            0 => Ok(Util::GenerateKeypair(GenerateKeypair::choose_generate_keypair()?)),
+            1 => Ok(Util::SaveKey(SaveKey::choose_save_key()?)),
+            2 => Ok(Util::ViewAccessKey(ViewAccessKey::choose_view_access_key()?)),
+            3 => Ok(Util::LatestBlock(LatestBlock::choose_latest_block()?)),
+            4 => Ok(Util::DecodeTransaction(DecodeTransaction::choose_decode_transaction()?)),
+            5 => Ok(Util::SignMessage(SignMessage::choose_sign_message()?)),
+            6 => Ok(Util::VerifySignature(VerifySignature::choose_verify_signature()?)),
+            _ => unreachable!("Error")
+        }
+    }
+}
+
+impl Utils {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match &self.util {
+            Util::GenerateKeypair(generate_keypair) => {
+                std::iter::once("generate-keypair".to_string()).chain(generate_keypair.to_cli_args()).collect()
+            },
+            Util::SaveKey(save_key) => {
+                std::iter::once("save-key".to_string()).chain(save_key.to_cli_args()).collect()
+            },
+            Util::ViewAccessKey(view_access_key) => {
+                std::iter::once("view-access-key".to_string()).chain(view_access_key.to_cli_args()).collect()
+            },
+            Util::LatestBlock(latest_block) => {
+                std::iter::once("latest-block".to_string()).chain(latest_block.to_cli_args()).collect()
+            },
+            Util::DecodeTransaction(decode_transaction) => {
+                std::iter::once("decode-transaction".to_string()).chain(decode_transaction.to_cli_args()).collect()
+            },
+            Util::SignMessage(sign_message) => {
+                std::iter::once("sign-message".to_string()).chain(sign_message.to_cli_args()).collect()
+            },
+            Util::VerifySignature(verify_signature) => {
+                std::iter::once("verify-signature".to_string()).chain(verify_signature.to_cli_args()).collect()
+            }
+        }
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        match self.util {
+            Util::GenerateKeypair(generate_keypair) => {
+                generate_keypair.process(output_format).await
+            },
+            Util::SaveKey(save_key) => {
+                save_key.process(output_format).await
+            },
+            Util::ViewAccessKey(view_access_key) => {
+                view_access_key.process(output_format).await
+            },
+            Util::LatestBlock(latest_block) => {
+                latest_block.process(output_format).await
+            },
+            Util::DecodeTransaction(decode_transaction) => {
+                decode_transaction.process(output_format).await
+            },
+            Util::SignMessage(sign_message) => {
+                sign_message.process(output_format).await
+            },
+            Util::VerifySignature(verify_signature) => {
+                verify_signature.process(output_format).await
             }
         }
     }
@@ -0,0 +1,166 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Confirm,
+    Input,
+    Password,
+};
+use crate::common::PromptAnswer;
+
+
+/// Store a secret key in the local keychain
+/// (`~/.near-credentials/<network>/<account>.json`), optionally encrypted with
+/// a passphrase
+pub struct SaveKey {
+    pub network_id: crate::common::NetworkId,
+    pub account_id: String,
+    pub secret_key: crate::common::SecretKey,
+    pub encrypt: bool,
+    pub force: bool,
+}
+
+impl std::fmt::Debug for SaveKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaveKey")
+            .field("network_id", &self.network_id)
+            .field("account_id", &self.account_id)
+            .field("secret_key", &crate::common::REDACTED_SECRET)
+            .field("encrypt", &self.encrypt)
+            .field("force", &self.force)
+            .finish()
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSaveKey {
+    #[structopt(long, possible_values = crate::common::NetworkId::VARIANTS)]
+    network: Option<crate::common::NetworkId>,
+    #[structopt(long)]
+    account_id: Option<crate::common::ValidAccountId>,
+    #[structopt(long)]
+    secret_key: Option<crate::common::SecretKey>,
+    /// Encrypt the key with a passphrase (it is asked for interactively)
+    #[structopt(long)]
+    encrypt: bool,
+    /// Replace the key which is already stored for the account
+    #[structopt(long)]
+    force: bool,
+}
+
+impl From<CliSaveKey> for SaveKey {
+    fn from(item: CliSaveKey) -> Self {
+        let network_id: crate::common::NetworkId = match item.network {
+            Some(cli_network_id) => cli_network_id,
+            None => crate::common::input_network_id().unwrap_or_else(|err| err.exit())
+        };
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id.into_inner(),
+            None => SaveKey::input_account_id().unwrap_or_else(|err| err.exit())
+        };
+        let secret_key: crate::common::SecretKey = match item.secret_key {
+            Some(cli_secret_key) => cli_secret_key,
+            None => SaveKey::input_secret_key().unwrap_or_else(|err| err.exit())
+        };
+        SaveKey {
+            network_id,
+            account_id,
+            secret_key,
+            encrypt: item.encrypt,
+            force: item.force,
+        }
+    }
+}
+
+impl SaveKey {
+    pub fn choose_save_key() -> Result<Self, crate::common::CliError> {
+        let network_id = crate::common::input_network_id()?;
+        let account_id = SaveKey::input_account_id()?;
+        let secret_key = SaveKey::input_secret_key()?;
+        let encrypt = SaveKey::input_encrypt()?;
+        Ok(SaveKey {
+            network_id,
+            account_id,
+            secret_key,
+            encrypt,
+            force: false,
+        })
+    }
+    pub fn input_account_id() -> Result<String, crate::common::CliError> {
+        eprintln!();
+        let account_id: crate::common::ValidAccountId = Input::new()
+            .with_prompt("Which account does the key belong to?")
+            .interact_text()
+            .answer()?;
+        Ok(account_id.into_inner())
+    }
+    pub fn input_secret_key() -> Result<crate::common::SecretKey, crate::common::CliError> {
+        crate::common::input_secret("Enter the secret key")
+    }
+    pub fn input_encrypt() -> Result<bool, crate::common::CliError> {
+        Confirm::new()
+            .with_prompt("Do you want to encrypt the key with a passphrase?")
+            .default(true)
+            .interact_on(&dialoguer::console::Term::stderr())
+            .answer()
+    }
+    pub fn input_new_passphrase() -> Result<zeroize::Zeroizing<String>, crate::common::CliError> {
+        Password::new()
+            .with_prompt("Enter a passphrase for the key")
+            .with_confirmation("Repeat the passphrase", "The passphrases do not match")
+            .interact()
+            .map(zeroize::Zeroizing::new)
+            .answer()
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--network".to_string(),
+            self.network_id.to_string(),
+            "--account-id".to_string(),
+            self.account_id.clone(),
+            "--secret-key".to_string(),
+            crate::common::REDACTED_SECRET.to_string(),
+        ];
+        if self.encrypt {
+            args.push("--encrypt".to_string());
+        };
+        if self.force {
+            args.push("--force".to_string());
+        };
+        args
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("save-key")?;
+        let passphrase: Option<zeroize::Zeroizing<String>> = if self.encrypt {
+            Some(SaveKey::input_new_passphrase()?)
+        } else {
+            None
+        };
+        let key_file_path = crate::keystore::save_secret_key(
+            self.network_id,
+            &self.account_id,
+            &self.secret_key,
+            passphrase.as_ref().map(|passphrase| passphrase.as_str()),
+            self.force,
+        )?;
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("The key for {} is saved to {}", self.account_id, key_file_path.display());
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "network": self.network_id.to_string(),
+                        "account_id": self.account_id,
+                        "public_key": self.secret_key.public_key().to_string(),
+                        "encrypted": self.encrypt,
+                        "key_file": key_file_path.display().to_string(),
+                    }))
+                    .expect("Saved key output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Select,
+    Input,
+    theme::ColorfulTheme,
+};
+use crate::common::PromptAnswer;
+
+
+/// Sign an arbitrary message with a NEAR key (the message bytes are signed as
+/// they are, the way off-chain authentication expects it)
+pub struct SignMessage {
+    pub signer_secret_key: crate::common::SecretKey,
+    /// Set when the key has been derived from a seed phrase
+    pub seed_phrase_hd_path: Option<slip10::BIP32Path>,
+    pub message: Vec<u8>,
+    pub message_file: Option<std::path::PathBuf>,
+    pub format: crate::common::SignatureFormat,
+}
+
+impl std::fmt::Debug for SignMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignMessage")
+            .field("signer_secret_key", &crate::common::REDACTED_SECRET)
+            .field(
+                "seed_phrase_hd_path",
+                &self.seed_phrase_hd_path.as_ref().map(crate::common::bip32path_to_string),
+            )
+            .field("message", &String::from_utf8_lossy(&self.message))
+            .field("message_file", &self.message_file)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignMessage {
+    /// The message to sign ("-" to read it from stdin)
+    message: Option<String>,
+    #[structopt(long, parse(from_os_str), conflicts_with = "message")]
+    file: Option<std::path::PathBuf>,
+    #[structopt(long, conflicts_with = "seed-phrase")]
+    signer_secret_key: Option<crate::common::SecretKey>,
+    #[structopt(long)]
+    seed_phrase: Option<crate::common::SeedPhrase>,
+    #[structopt(long, default_value = "m/44'/397'/0'")]
+    seed_phrase_hd_path: slip10::BIP32Path,
+    #[structopt(long, default_value = "base58", possible_values = crate::common::SignatureFormat::VARIANTS)]
+    encoding: crate::common::SignatureFormat,
+}
+
+impl From<CliSignMessage> for SignMessage {
+    fn from(item: CliSignMessage) -> Self {
+        let message_file = match item.message.as_deref() {
+            Some("-") => Some(std::path::PathBuf::from("-")),
+            _ => item.file,
+        };
+        let message: Vec<u8> = match (item.message, &message_file) {
+            (_, Some(cli_file)) => crate::common::read_message(cli_file)
+                .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit()),
+            (Some(cli_message), None) => cli_message.into_bytes(),
+            (None, None) => SignMessage::input_message().unwrap_or_else(|err| err.exit())
+        };
+        let (signer_secret_key, seed_phrase_hd_path) = match (item.signer_secret_key, item.seed_phrase) {
+            (Some(cli_signer_secret_key), _) => (cli_signer_secret_key, None),
+            (None, Some(cli_seed_phrase)) => {
+                let signer_secret_key = crate::common::get_secret_key_from_seed_phrase(
+                    cli_seed_phrase.as_str(),
+                    &item.seed_phrase_hd_path,
+                )
+                .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit());
+                (signer_secret_key, Some(item.seed_phrase_hd_path))
+            },
+            (None, None) => SignMessage::input_signer_secret_key().unwrap_or_else(|err| err.exit())
+        };
+        SignMessage {
+            signer_secret_key,
+            seed_phrase_hd_path,
+            message,
+            message_file,
+            format: item.encoding,
+        }
+    }
+}
+
+impl SignMessage {
+    pub fn choose_sign_message() -> Result<Self, crate::common::CliError> {
+        let message = SignMessage::input_message()?;
+        let (signer_secret_key, seed_phrase_hd_path) = SignMessage::input_signer_secret_key()?;
+        let format = crate::common::input_signature_format()?;
+        Ok(SignMessage {
+            signer_secret_key,
+            seed_phrase_hd_path,
+            message,
+            message_file: None,
+            format,
+        })
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format: &str = self.format.into();
+        let mut args = match &self.message_file {
+            Some(message_file) => vec!["--file".to_string(), message_file.to_string_lossy().into_owned()],
+            None => vec![String::from_utf8_lossy(&self.message).into_owned()],
+        };
+        match &self.seed_phrase_hd_path {
+            Some(seed_phrase_hd_path) => args.extend(vec![
+                "--seed-phrase".to_string(),
+                crate::common::REDACTED_SECRET.to_string(),
+                "--seed-phrase-hd-path".to_string(),
+                crate::common::bip32path_to_string(seed_phrase_hd_path),
+            ]),
+            None => args.extend(vec![
+                "--signer-secret-key".to_string(),
+                crate::common::REDACTED_SECRET.to_string(),
+            ]),
+        };
+        args.extend(vec!["--encoding".to_string(), format.to_string()]);
+        args
+    }
+    pub fn input_message() -> Result<Vec<u8>, crate::common::CliError> {
+        let message: String = Input::new()
+            .with_prompt("Enter the message to sign")
+            .interact_text()
+            .answer()?;
+        Ok(message.into_bytes())
+    }
+    /// Returns the HD path as well when the key is derived from a seed phrase
+    pub fn input_signer_secret_key(
+    ) -> Result<(crate::common::SecretKey, Option<slip10::BIP32Path>), crate::common::CliError> {
+        eprintln!();
+        let choose_key = vec![
+            "Secret key (ed25519:...)",
+            "Seed phrase",
+        ];
+        let select_key = crate::common::interact_select(
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("How do you want to provide the signing key?")
+                .items(&choose_key)
+                .default(0),
+        )?;
+        match select_key {
+            0 => Ok((crate::common::input_secret("Enter the private key")?, None)),
+            1 => {
+                let seed_phrase: crate::common::SeedPhrase = crate::common::input_secret("Enter the seed phrase")?;
+                let seed_phrase_hd_path = crate::utils_subcommand::generate_keypair_subcommand::GenerateKeypair::input_seed_phrase_hd_path()?;
+                let signer_secret_key =
+                    crate::common::get_secret_key_from_seed_phrase(seed_phrase.as_str(), &seed_phrase_hd_path)
+                        .map_err(crate::common::CliError::ParseError)?;
+                Ok((signer_secret_key, Some(seed_phrase_hd_path)))
+            },
+            _ => unreachable!("Error")
+        }
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("sign-message")?;
+        log::debug!("SignMessage process: self:       {:?}", &self);
+        // secp256k1 keys can only sign 32-byte hashes
+        if self.signer_secret_key.key_type() != near_crypto::KeyType::ED25519 {
+            return Err(crate::common::CliError::SigningError(
+                "Only ed25519 keys can sign arbitrary messages".to_string(),
+            ));
+        };
+        let public_key = self.signer_secret_key.public_key();
+        let signature = self.signer_secret_key.sign(&self.message);
+        let encoded_signature = self.format.encode(&signature);
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("Public key:  {}", public_key);
+                println!("Signature:   {}", encoded_signature);
+            }
+            crate::common::OutputFormat::Json => {
+                let format: &str = self.format.into();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "public_key": public_key.to_string(),
+                        "signature": encoded_signature,
+                        "signature_format": format,
+                    }))
+                    .expect("Signature output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        Ok(())
+    }
+}
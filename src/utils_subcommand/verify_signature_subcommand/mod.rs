@@ -0,0 +1,159 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+use dialoguer::{
+    Input,
+};
+use crate::common::PromptAnswer;
+
+
+/// Check a signature of an arbitrary message against a NEAR public key
+#[derive(Debug)]
+pub struct VerifySignature {
+    pub public_key: near_crypto::PublicKey,
+    pub message: Vec<u8>,
+    pub message_file: Option<std::path::PathBuf>,
+    pub signature: near_crypto::Signature,
+    pub format: crate::common::SignatureFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliVerifySignature {
+    /// The signed message ("-" to read it from stdin)
+    message: Option<String>,
+    #[structopt(long, parse(from_os_str), conflicts_with = "message")]
+    file: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    public_key: Option<crate::common::PublicKey>,
+    #[structopt(long)]
+    signature: Option<String>,
+    #[structopt(long, default_value = "base58", possible_values = crate::common::SignatureFormat::VARIANTS)]
+    encoding: crate::common::SignatureFormat,
+}
+
+impl From<CliVerifySignature> for VerifySignature {
+    fn from(item: CliVerifySignature) -> Self {
+        let message_file = match item.message.as_deref() {
+            Some("-") => Some(std::path::PathBuf::from("-")),
+            _ => item.file,
+        };
+        let message: Vec<u8> = match (item.message, &message_file) {
+            (_, Some(cli_file)) => crate::common::read_message(cli_file)
+                .unwrap_or_else(|err| crate::common::CliError::IoError(err).exit()),
+            (Some(cli_message), None) => cli_message.into_bytes(),
+            (None, None) => VerifySignature::input_message().unwrap_or_else(|err| err.exit())
+        };
+        let public_key: near_crypto::PublicKey = match item.public_key {
+            Some(cli_public_key) => cli_public_key.into_inner(),
+            None => VerifySignature::input_public_key().unwrap_or_else(|err| err.exit())
+        };
+        let signature: near_crypto::Signature = match item.signature {
+            Some(cli_signature) => item.encoding.decode(&cli_signature, public_key.key_type())
+                .unwrap_or_else(|err| crate::common::CliError::ParseError(err).exit()),
+            None => VerifySignature::input_signature(item.encoding, public_key.key_type())
+                .unwrap_or_else(|err| err.exit())
+        };
+        VerifySignature {
+            public_key,
+            message,
+            message_file,
+            signature,
+            format: item.encoding,
+        }
+    }
+}
+
+impl VerifySignature {
+    pub fn choose_verify_signature() -> Result<Self, crate::common::CliError> {
+        let message = VerifySignature::input_message()?;
+        let public_key = VerifySignature::input_public_key()?;
+        let format = crate::common::input_signature_format()?;
+        let signature = VerifySignature::input_signature(format, public_key.key_type())?;
+        Ok(VerifySignature {
+            public_key,
+            message,
+            message_file: None,
+            signature,
+            format,
+        })
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let format: &str = self.format.into();
+        let mut args = match &self.message_file {
+            Some(message_file) => vec!["--file".to_string(), message_file.to_string_lossy().into_owned()],
+            None => vec![String::from_utf8_lossy(&self.message).into_owned()],
+        };
+        args.extend(vec![
+            "--public-key".to_string(),
+            self.public_key.to_string(),
+            "--signature".to_string(),
+            self.format.encode(&self.signature),
+            "--encoding".to_string(),
+            format.to_string(),
+        ]);
+        args
+    }
+    pub fn input_message() -> Result<Vec<u8>, crate::common::CliError> {
+        let message: String = Input::new()
+            .with_prompt("Enter the signed message")
+            .interact_text()
+            .answer()?;
+        Ok(message.into_bytes())
+    }
+    pub fn input_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+        let public_key: crate::common::PublicKey = Input::new()
+            .with_prompt("Enter the public key of the signer")
+            .interact_text()
+            .answer()?;
+        Ok(public_key.into_inner())
+    }
+    pub fn input_signature(
+        format: crate::common::SignatureFormat,
+        key_type: near_crypto::KeyType,
+    ) -> Result<near_crypto::Signature, crate::common::CliError> {
+        loop {
+            let input: String = Input::new()
+                .with_prompt("Enter the signature")
+                .interact_text()
+                .answer()?;
+            match format.decode(&input, key_type) {
+                Ok(signature) => break Ok(signature),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("verify-signature")?;
+        log::debug!("VerifySignature process: self:       {:?}", &self);
+        let is_signature_valid = self.signature.verify(&self.message, &self.public_key);
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "Signature check:  {}",
+                    if is_signature_valid {
+                        "valid for the message and the public key"
+                    } else {
+                        "INVALID for the message and the public key"
+                    }
+                );
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "public_key": self.public_key.to_string(),
+                        "signature": self.signature.to_string(),
+                        "signature_valid": is_signature_valid,
+                    }))
+                    .expect("Signature check output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        if !is_signature_valid {
+            return Err(crate::common::CliError::SigningError(
+                "The signature does not match the message and the public key".to_string(),
+            ));
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use structopt::StructOpt;
+use dialoguer::{
+    Input,
+};
+use crate::common::PromptAnswer;
+
+use crate::command::select_network::{
+    CliSelectNetwork,
+    choose_connection_config,
+};
+
+
+/// View the access key of an account, including the nonce to use for the
+/// next transaction signed with it (offline signing needs it)
+#[derive(Debug)]
+pub struct ViewAccessKey {
+    pub account_id: String,
+    pub public_key: near_crypto::PublicKey,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub bundle: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliViewAccessKey {
+    account_id: Option<crate::common::ValidAccountId>,
+    public_key: Option<crate::common::PublicKey>,
+    /// Start a transaction bundle file with the nonce and the block hash for
+    /// offline signing
+    #[structopt(long, parse(from_os_str))]
+    bundle: Option<std::path::PathBuf>,
+    #[structopt(subcommand)]
+    selected_network: Option<CliSelectNetwork>,
+}
+
+impl From<CliViewAccessKey> for ViewAccessKey {
+    fn from(item: CliViewAccessKey) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id.into_inner(),
+            None => ViewAccessKey::input_account_id().unwrap_or_else(|err| err.exit())
+        };
+        let public_key: near_crypto::PublicKey = match item.public_key {
+            Some(cli_public_key) => cli_public_key.into_inner(),
+            None => ViewAccessKey::input_public_key().unwrap_or_else(|err| err.exit())
+        };
+        let connection_config = match item.selected_network {
+            Some(cli_selected_network) => crate::common::ConnectionConfig::from(cli_selected_network),
+            None => choose_connection_config().unwrap_or_else(|err| err.exit())
+        };
+        ViewAccessKey {
+            account_id,
+            public_key,
+            connection_config,
+            bundle: item.bundle,
+        }
+    }
+}
+
+impl ViewAccessKey {
+    pub fn choose_view_access_key() -> Result<Self, crate::common::CliError> {
+        let account_id = ViewAccessKey::input_account_id()?;
+        let public_key = ViewAccessKey::input_public_key()?;
+        let connection_config = choose_connection_config()?;
+        Ok(ViewAccessKey {
+            account_id,
+            public_key,
+            connection_config,
+            bundle: None,
+        })
+    }
+    pub fn input_account_id() -> Result<String, crate::common::CliError> {
+        eprintln!();
+        let account_id: crate::common::ValidAccountId = Input::new()
+            .with_prompt("Which account does the access key belong to?")
+            .interact_text()
+            .answer()?;
+        Ok(account_id.into_inner())
+    }
+    pub fn input_public_key() -> Result<near_crypto::PublicKey, crate::common::CliError> {
+        let public_key: crate::common::PublicKey = Input::new()
+            .with_prompt("Enter the public key of the access key")
+            .interact_text()
+            .answer()?;
+        Ok(public_key.into_inner())
+    }
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.account_id.clone(), self.public_key.to_string()];
+        if let Some(bundle) = &self.bundle {
+            args.extend(vec!["--bundle".to_string(), bundle.to_string_lossy().into_owned()]);
+        };
+        args.extend(self.connection_config.to_cli_args());
+        args
+    }
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::common::CliResult {
+        output_format.reject_qr("view-access-key")?;
+        log::info!(
+            "Fetching the access key {} of {} from {}",
+            self.public_key,
+            self.account_id,
+            self.connection_config.url
+        );
+        let access_key_response = self.connection_config
+            .rpc_client()
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: self.account_id.clone(),
+                    public_key: self.public_key.clone(),
+                },
+            })
+            .await
+            .map_err(|err| {
+                crate::common::CliError::RpcError(format!("Failed to fetch the access key: {:?}", err))
+            })?;
+        let access_key =
+            if let near_primitives::views::QueryResponseKind::AccessKey(access_key) = access_key_response.kind {
+                access_key
+            } else {
+                return Err(crate::common::CliError::RpcError(
+                    "Unexpected response to the access key query".to_string()
+                ));
+            };
+        let next_nonce = access_key.nonce + 1;
+        if let Some(ref bundle) = self.bundle {
+            crate::bundle::TransactionBundle::prepare(
+                Some(self.connection_config.network_id),
+                &self.account_id,
+                &self.public_key,
+                next_nonce,
+                &access_key_response.block_hash,
+            )
+            .write(bundle)?;
+            eprintln!("The transaction bundle has been saved to {}", bundle.display());
+        };
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("Account ID:  {}", self.account_id);
+                println!("Public key:  {}", self.public_key);
+                println!("Nonce:       {}", access_key.nonce);
+                println!("Block hash:  {}", access_key_response.block_hash);
+                println!("Permission:  {:?}", access_key.permission);
+                println!();
+                println!("Offline transaction arguments:");
+                println!("--nonce {} --block-hash {}", next_nonce, access_key_response.block_hash);
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "account_id": self.account_id,
+                        "public_key": self.public_key.to_string(),
+                        "nonce": access_key.nonce,
+                        "next_nonce": next_nonce,
+                        "block_height": access_key_response.block_height,
+                        "block_hash": access_key_response.block_hash.to_string(),
+                        "permission": access_key.permission,
+                    }))
+                    .expect("Access key output is not expected to fail on serialization")
+                );
+            }
+            crate::common::OutputFormat::Qr => unreachable!("QR output has been rejected")
+        };
+        Ok(())
+    }
+}